@@ -0,0 +1,14 @@
+cmt Lists
+
+label .ENTRY
+    list_new scores 72 95 48
+    push scores 81
+    sort scores
+
+    len count scores
+    print "{count} scores: {scores}"
+
+    foreach score scores show_score
+
+label show_score
+    print "- {score}"
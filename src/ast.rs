@@ -1,10 +1,19 @@
+use std::{cmp::Ordering, collections::BTreeMap};
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+use crate::error::{ErrorKind, ScriptError};
 use crate::lexer::Line;
 
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub enum Types {
     String,
     Number,
+    Bool,
     Identifier,
+    List,
+    Map,
     Unknown,
 }
 
@@ -14,6 +23,203 @@ impl std::fmt::Display for Types {
     }
 }
 
+#[derive(Clone, Copy, Debug)]
+pub enum Number {
+    Integer(i64),
+    Float(f64),
+}
+
+impl Number {
+    #[must_use]
+    pub fn as_float(self) -> f64 {
+        match self {
+            #[allow(clippy::cast_precision_loss)]
+            Self::Integer(integer) => return integer as f64,
+            Self::Float(float) => return float,
+        }
+    }
+}
+
+// Integers are compared exactly, a Float on either side compares both as floats
+impl PartialOrd for Number {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Self::Integer(a), Self::Integer(b)) => return Some(a.cmp(b)),
+            _ => return self.as_float().partial_cmp(&other.as_float()),
+        }
+    }
+}
+
+impl PartialEq for Number {
+    fn eq(&self, other: &Self) -> bool {
+        return self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl std::fmt::Display for Number {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Integer(integer) => write!(f, "{integer}"),
+
+            // Always written so that it is read back as a Float
+            Self::Float(float) => {
+                if float.abs() >= 1e16 || (float.abs() < 1e-5 && *float != 0.0) {
+                    write!(f, "{float:e}")
+                } else if float.fract() == 0.0 {
+                    write!(f, "{float:.1}")
+                } else {
+                    write!(f, "{float}")
+                }
+            }
+        }
+    }
+}
+
+// Compiled once, every instruction parses its arguments
+static DECIMAL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^-?[0-9]+(_[0-9]+)*(\.[0-9]+(_[0-9]+)*)?([eE][+-]?[0-9]+)?$").unwrap()
+});
+static HEX_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^-?0[xX][0-9a-fA-F]+(_[0-9a-fA-F]+)*$").unwrap());
+static BINARY_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^-?0[bB][01]+(_[01]+)*$").unwrap());
+
+static IDENTIFIER_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[a-zA-Z_][a-zA-Z0-9_]*$").unwrap());
+
+// Whether `token` names a variable, `true` and `false` are Bools instead
+#[must_use]
+pub fn is_identifier(token: &str) -> bool {
+    return IDENTIFIER_RE.is_match(token) && token != "true" && token != "false"
+}
+
+// Parses number literals such as `-12`, `1_000`, `0xFF`, `0b1010`, `2.5` and `1e-3`
+#[must_use]
+pub fn parse_number(token: &str) -> Option<Number> {
+    let decimal_re = &*DECIMAL_RE;
+    let hex_re = &*HEX_RE;
+    let binary_re = &*BINARY_RE;
+
+    let (sign, digits) = match token.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", token),
+    };
+    let digits = digits.replace('_', "");
+
+    if hex_re.is_match(token) {
+        return i64::from_str_radix(&format!("{sign}{}", &digits[2..]), 16)
+            .ok()
+            .map(Number::Integer)
+    } else if binary_re.is_match(token) {
+        return i64::from_str_radix(&format!("{sign}{}", &digits[2..]), 2)
+            .ok()
+            .map(Number::Integer)
+    } else if decimal_re.is_match(token) {
+        let number = format!("{sign}{digits}");
+        if number.contains(['.', 'e', 'E']) {
+            return number
+                .parse::<f64>()
+                .ok()
+                .filter(|float| float.is_finite())
+                .map(Number::Float)
+        } else {
+            return number.parse::<i64>().ok().map(Number::Integer)
+        }
+    } else {
+        return None
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum Value {
+    // A single token as written in the source, e.g. `21`, `"Hello"` or a variable name
+    Literal(String),
+    List(Vec<Value>),
+    // Keys are kept sorted so maps always print the same way
+    Map(BTreeMap<String, Value>),
+}
+
+impl Value {
+    // Roughly how many bytes the value takes up, used by the memory limit
+    #[must_use]
+    pub fn size(&self) -> usize {
+        match self {
+            Self::Literal(token) => return token.len(),
+            Self::List(items) => return items.iter().map(Self::size).sum(),
+            Self::Map(entries) => {
+                return entries
+                    .iter()
+                    .map(|(key, value)| key.len() + value.size())
+                    .sum()
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Literal(token) => {
+                write!(
+                    f,
+                    "{}",
+                    unescape(&token.replace("{{", "{").replace("}}", "}"))
+                )
+            }
+
+            Self::List(items) => {
+                let items = items
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<String>>();
+                write!(f, "[{}]", items.join(", "))
+            }
+
+            Self::Map(entries) => {
+                let entries = entries
+                    .iter()
+                    .map(|(key, value)| format!("{key:?}: {value}"))
+                    .collect::<Vec<String>>();
+                write!(f, "{{{}}}", entries.join(", "))
+            }
+        }
+    }
+}
+
+// Turns string content back into a literal which will not be interpolated or unescaped again
+#[must_use]
+pub fn to_string_literal(content: &str) -> String {
+    let content = content
+        .replace('\\', "\\\\")
+        .replace('{', "{{")
+        .replace('}', "}}");
+    return format!("\"{content}\"")
+}
+
+// The escapes of a string literal, `\n` is a newline and `\\` a backslash. Other backslashes are
+// kept as they are
+#[must_use]
+pub fn unescape(string: &str) -> String {
+    let mut result = String::new();
+    let mut chars = string.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('\\', Some('n')) => {
+                chars.next();
+                result.push('\n');
+            }
+            ('\\', Some('\\')) => {
+                chars.next();
+                result.push('\\');
+            }
+            _ => result.push(c),
+        }
+    }
+
+    return result
+}
+
 #[derive(PartialEq, Eq, Debug, Clone)]
 pub struct Label {
     pub label_name: String,
@@ -40,3 +246,68 @@ pub fn get_code_from(labels: Vec<Label>, label_name: String) -> Vec<(usize, Line
 
     return Vec::new()
 }
+
+// Splits the lines of a script into the labels they belong to
+pub fn get_labels(lexed_code: &[Line]) -> Result<Vec<Label>, ScriptError> {
+    let mut labels: Vec<Label> = vec![];
+    let mut current_label: String = String::new();
+    let mut label_code: Vec<(usize, Line)> = Vec::new();
+
+    for (line_number, line) in lexed_code.iter().enumerate() {
+        let line_number = line_number + 1;
+        let line: Vec<String> = line.clone().0;
+        if line.is_empty() {
+            continue;
+        }
+        let string_line = line.clone().join(" ");
+
+        let command: String = line[0].clone();
+        let args: Vec<String> = line[1..].to_vec().clone();
+        let args_len = args.len();
+
+        match command.clone().as_str() {
+            "label" => {
+                if args_len != 1 {
+                    return Err(ScriptError {
+                        kind: ErrorKind::Arguments,
+                        message: format!("Expected 1 argument, but got {args_len}"),
+                        line_number,
+                        line_code: string_line,
+                        notes: vec!["[Usage] label <label_name>".to_string()],
+                    });
+                }
+
+                labels.push(Label {
+                    label_name: current_label.clone(),
+                    label_code,
+                });
+                label_code = Vec::new();
+
+                let label_name = args[0].clone();
+                current_label = label_name.clone();
+
+                if has_label(labels.clone(), label_name.clone()) {
+                    return Err(ScriptError {
+                        kind: ErrorKind::Label,
+                        message: format!("Label `{label_name}` already exists."),
+                        line_number,
+                        line_code: string_line,
+                        notes: vec!["[Help] Do not use an existing label name.".to_string()],
+                    });
+                }
+            }
+
+            _ => {
+                label_code.push((line_number, Line(line.clone())));
+            }
+        }
+    }
+
+    // The last line may be empty, e.g. when the script ends with a comment
+    labels.push(Label {
+        label_name: current_label,
+        label_code,
+    });
+
+    return Ok(labels)
+}
@@ -0,0 +1,21 @@
+#![warn(
+    clippy::all,
+    clippy::restriction,
+    clippy::pedantic,
+    clippy::nursery,
+    clippy::cargo
+)]
+
+use std::io;
+use std::process;
+
+// A language server for `.ll` scripts, speaking LSP over stdin and stdout
+fn main() {
+    let mut input = io::stdin().lock();
+    let mut output = io::stdout().lock();
+
+    if let Err(error) = script_ll_2::lsp::serve(&mut input, &mut output) {
+        eprintln!("[Error] {error}");
+        process::exit(1);
+    }
+}
@@ -0,0 +1,52 @@
+use crate::ast::Label;
+use crate::commands::{self, ArgumentKind};
+use crate::error::{ErrorKind, ScriptError};
+
+// Finds mistakes without running the script: unknown commands, wrong numbers of arguments,
+// invalid variable names and jumps to labels which do not exist
+#[must_use]
+pub fn check(labels: &[Label]) -> Vec<ScriptError> {
+    let mut errors = Vec::new();
+
+    for label in labels {
+        for (line_number, line) in &label.label_code {
+            let line_code = line.0.join(" ");
+            if let Err(mut error) = check_command(labels, &line.0) {
+                error.line_number = *line_number;
+                error.line_code = line_code;
+                errors.push(error);
+            }
+        }
+    }
+
+    return errors
+}
+
+fn check_command(labels: &[Label], words: &[String]) -> Result<(), ScriptError> {
+    let command = &words[0];
+    let arguments = &words[1..];
+
+    let Some(info) = commands::find(command) else {
+        return Err(ScriptError {
+            kind: ErrorKind::Syntax,
+            message: format!("Unknown command `{command}`"),
+            line_number: 0,
+            line_code: String::new(),
+            notes: Vec::new(),
+        });
+    };
+
+    info.check_arguments(arguments, labels)?;
+
+    // `cmd_eq` runs another command, which is checked the same way
+    let nested = info
+        .parameters
+        .iter()
+        .position(|parameter| parameter.kind == ArgumentKind::Command);
+    match nested {
+        Some(index) if index < arguments.len() => {
+            return check_command(labels, &arguments[index..])
+        }
+        _ => return Ok(()),
+    }
+}
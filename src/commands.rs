@@ -0,0 +1,851 @@
+use std::sync::LazyLock;
+
+use crate::ast::{self, has_label, Label};
+use crate::error::{ErrorKind, ScriptError};
+
+// What a parameter accepts
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ArgumentKind {
+    // The name of a variable, e.g. a destination, passed on without looking it up
+    Name,
+    // The name of a label, which has to exist
+    Label,
+    // A command followed by its own arguments, as in `cmd_eq`
+    Command,
+    Any,
+    String,
+    Number,
+    Bool,
+    List,
+    Map,
+}
+
+impl std::fmt::Display for ArgumentKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Name => write!(f, "variable name"),
+            Self::Label => write!(f, "label name"),
+            Self::Command => write!(f, "command"),
+            Self::Any => write!(f, "value"),
+            Self::String => write!(f, "String"),
+            Self::Number => write!(f, "Number"),
+            Self::Bool => write!(f, "Bool"),
+            Self::List => write!(f, "List"),
+            Self::Map => write!(f, "Map"),
+        }
+    }
+}
+
+// What a command does with an argument, beyond what its kind accepts
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Role {
+    // Values are looked up when they name a variable, names and labels are used as they are
+    Plain,
+    // The label or variable is made or assigned by the command, as by `label` or a destination
+    Defines,
+    // The variable name is looked up, e.g. the list of `push`
+    Reads,
+    // One of the two values a comparison looks at, as for `test_eq`
+    Compared,
+    // Never looked at, e.g. the words of `cmt`
+    Ignored,
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Parameter {
+    pub name: String,
+    pub kind: ArgumentKind,
+    pub role: Role,
+    // Optional and variadic parameters may only follow required ones
+    pub optional: bool,
+    // Takes every remaining argument, including none
+    pub variadic: bool,
+}
+
+impl Parameter {
+    #[must_use]
+    pub fn required(name: &str, kind: ArgumentKind) -> Self {
+        return Self {
+            name: name.to_string(),
+            kind,
+            role: Role::Plain,
+            optional: false,
+            variadic: false,
+        }
+    }
+
+    #[must_use]
+    pub fn optional(name: &str, kind: ArgumentKind) -> Self {
+        return Self {
+            optional: true,
+            ..Self::required(name, kind)
+        }
+    }
+
+    #[must_use]
+    pub fn variadic(name: &str, kind: ArgumentKind) -> Self {
+        return Self {
+            variadic: true,
+            ..Self::required(name, kind)
+        }
+    }
+
+    const fn role(mut self, role: Role) -> Self {
+        self.role = role;
+        return self
+    }
+}
+
+// Groups commands in `--h`, with a note shown after the last command of the group
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Category {
+    Variables,
+    ControlFlow,
+    Tests,
+    Bools,
+    Arithmetic,
+    Types,
+    Lists,
+    Maps,
+    Errors,
+    Files,
+    Output,
+    System,
+    Host,
+}
+
+impl Category {
+    #[must_use]
+    pub const fn note(self) -> Option<&'static str> {
+        match self {
+            Self::Tests => return Some("(The result of a test, true or false, is stored in [destination], or in TEMP if omitted)"),
+            Self::Files => return Some("(Paths are relative to the directory of the script, failures can be caught using try)"),
+            _ => return None,
+        }
+    }
+}
+
+impl std::fmt::Display for Category {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::ControlFlow => write!(f, "Control flow"),
+            _ => write!(f, "{self:?}"),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct CommandInfo {
+    pub name: String,
+    pub category: Category,
+    pub parameters: Vec<Parameter>,
+    pub help: String,
+    pub examples: Vec<String>,
+    // Errors only this command gives, see `all_errors` for every one of them
+    pub errors: Vec<(ErrorKind, String)>,
+}
+
+impl CommandInfo {
+    // e.g. `map_get <destination> <map_name> <key> [default]`
+    #[must_use]
+    pub fn usage(&self) -> String {
+        let mut usage = self.name.clone();
+        for parameter in &self.parameters {
+            if parameter.optional {
+                usage += &format!(" [{}]", parameter.name);
+            } else if parameter.variadic {
+                usage += &format!(" <{}>...", parameter.name);
+            } else {
+                usage += &format!(" <{}>", parameter.name);
+            }
+        }
+
+        return usage
+    }
+
+    fn example(mut self, code: &str) -> Self {
+        self.examples.push(code.to_string());
+        return self
+    }
+
+    fn error(mut self, kind: ErrorKind, when: &str) -> Self {
+        self.errors.push((kind, when.to_string()));
+        return self
+    }
+
+    // Every error the command can give and when, including the ones coming from its parameters
+    #[must_use]
+    pub fn all_errors(&self) -> Vec<(ErrorKind, String)> {
+        let mut errors = Vec::new();
+
+        let takes_any_arguments = self.parameters.iter().all(|parameter| parameter.variadic);
+        if !takes_any_arguments {
+            errors.push((
+                ErrorKind::Arguments,
+                "the number of arguments is wrong".to_string(),
+            ));
+        }
+
+        for parameter in &self.parameters {
+            match (parameter.kind, parameter.role) {
+                (_, Role::Ignored) => {}
+                (ArgumentKind::Name, _) => errors.push((
+                    ErrorKind::Type,
+                    format!("`<{}>` is not a valid variable name", parameter.name),
+                )),
+                // `label` creates its label instead of jumping to it
+                (ArgumentKind::Label, Role::Defines) => {}
+                (ArgumentKind::Label, _) => errors.push((
+                    ErrorKind::Label,
+                    format!("label `<{}>` does not exist", parameter.name),
+                )),
+                (ArgumentKind::Number | ArgumentKind::Bool, _) => errors.push((
+                    ErrorKind::Type,
+                    format!("`<{}>` is not a {}", parameter.name, parameter.kind),
+                )),
+                _ => {}
+            }
+        }
+
+        // Values which name a variable are looked up, as are lists and maps
+        let reads_variables =
+            self.parameters
+                .iter()
+                .any(|parameter| match (parameter.kind, parameter.role) {
+                    (_, Role::Ignored) | (ArgumentKind::Label | ArgumentKind::Command, _) => false,
+                    (ArgumentKind::Name, role) => role == Role::Reads,
+                    _ => true,
+                });
+        if reads_variables {
+            errors.push((
+                ErrorKind::Variable,
+                "a variable used as an argument does not exist".to_string(),
+            ));
+        }
+
+        errors.extend(self.errors.iter().cloned());
+        return errors
+    }
+
+    // The usage followed by the help text, lined up the way `--h` shows it
+    #[must_use]
+    pub fn help_line(&self) -> String {
+        return format!("{:<47} {}", self.usage(), self.help)
+    }
+
+    // Checks the number of arguments, variable names and labels, which works without running
+    // the script. The error has no line yet, the caller knows where the command is.
+    pub fn check_arguments(
+        &self,
+        arguments: &[String],
+        labels: &[Label],
+    ) -> Result<(), ScriptError> {
+        let no_of_args = arguments.len();
+        let min_args = self
+            .parameters
+            .iter()
+            .filter(|parameter| !parameter.optional && !parameter.variadic)
+            .count();
+        let max_args = if self.parameters.iter().any(|parameter| parameter.variadic) {
+            None
+        } else {
+            Some(self.parameters.len())
+        };
+
+        if no_of_args < min_args || max_args.is_some_and(|max_args| no_of_args > max_args) {
+            let expected = match max_args {
+                None if min_args == 1 => "at least 1 argument".to_string(),
+                None => format!("at least {min_args} arguments"),
+                Some(1) if min_args == 1 => "exactly 1 argument".to_string(),
+                Some(max_args) if max_args == min_args => format!("exactly {min_args} arguments"),
+                Some(max_args) if max_args == min_args + 1 => {
+                    format!("{min_args} or {max_args} arguments")
+                }
+                Some(max_args) => format!("between {min_args} and {max_args} arguments"),
+            };
+            return Err(error(
+                ErrorKind::Arguments,
+                format!("Expected {expected}, found {no_of_args}"),
+                vec![format!("[Usage] {}", self.usage())],
+            ));
+        }
+
+        for (argument, parameter) in arguments.iter().zip(&self.parameters) {
+            match parameter.kind {
+                ArgumentKind::Name if !ast::is_identifier(argument) => {
+                    return Err(error(
+                        ErrorKind::Type,
+                        format!(
+                            "Expected `{}` to be a variable name, not `{argument}`",
+                            parameter.name
+                        ),
+                        vec![format!("[Usage] {}", self.usage())],
+                    ));
+                }
+
+                ArgumentKind::Label if !has_label(labels.to_vec(), argument.clone()) => {
+                    return Err(error(
+                        ErrorKind::Label,
+                        format!("Label `{argument}` does not exist."),
+                        vec!["[Note] The label needs to exist".to_string()],
+                    ));
+                }
+
+                // Everything after the command belongs to it
+                ArgumentKind::Command => break,
+
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// The line is filled in by whoever checked the command
+fn error(kind: ErrorKind, message: String, notes: Vec<String>) -> ScriptError {
+    return ScriptError {
+        kind,
+        message,
+        line_number: 0,
+        line_code: String::new(),
+        notes,
+    }
+}
+
+fn command(name: &str, category: Category, parameters: Vec<Parameter>, help: &str) -> CommandInfo {
+    return CommandInfo {
+        name: name.to_string(),
+        category,
+        parameters,
+        help: help.to_string(),
+        examples: Vec::new(),
+        errors: Vec::new(),
+    }
+}
+
+// Every built-in command, in the order `--h` lists them
+static COMMANDS: LazyLock<Vec<CommandInfo>> = LazyLock::new(|| {
+    use ArgumentKind::{Any, Bool, Command, Label, Name, Number, String};
+    use Category::{
+        Arithmetic, Bools, ControlFlow, Errors, Files, Lists, Maps, Output, System, Tests, Types,
+        Variables,
+    };
+    use ErrorKind::{
+        Arithmetic as ArithmeticError, Assertion, Conversion, Index, Io, Key, Permission, Thrown,
+        Type, Variable,
+    };
+    let required = Parameter::required;
+    let optional = Parameter::optional;
+    let variadic = Parameter::variadic;
+    let destination = || return required("destination", Name).role(Role::Defines);
+    let optional_destination = || return optional("destination", Name).role(Role::Defines);
+    let compared = |name: &str, kind| return required(name, kind).role(Role::Compared);
+    let list_name = || return required("list_name", Name).role(Role::Reads);
+    let map_name = || return required("map_name", Name).role(Role::Reads);
+
+    let not_a_list = "`<list_name>` is not a List";
+    let not_a_map = "`<map_name>` is not a Map";
+    let overflow = "the result does not fit into an Integer";
+    let past_the_end = "`<index>` is past the end of the list";
+
+    return vec![
+        command(
+            "label",
+            ControlFlow,
+            vec![required("label_name", Label).role(Role::Defines)],
+            "Creates a label",
+        )
+            .example("label greet\n    print \"Hello\"")
+            .error(ErrorKind::Label, "a label named `<label_name>` already exists"),
+        command("jmp", ControlFlow, vec![required("label_name", Label)], "Jumps to a label")
+            .example("jmp greet"),
+        command(
+            "cmd_eq",
+            ControlFlow,
+            vec![
+                compared("value1", Any),
+                compared("value2", Any),
+                required("command", Command),
+                variadic("args", Any),
+            ],
+            "Executes <command> with arguments <args> if <value1> is equal to <value2>, numbers are compared by value and true and false equal 1 and 0",
+        )
+        .example("test_gt score 10\ncmd_eq TEMP true print \"You win!\""),
+        command(
+            "foreach",
+            ControlFlow,
+            vec![
+                required("item_name", Name).role(Role::Defines),
+                list_name(),
+                required("label_name", Label),
+            ],
+            "Jumps to <label_name> once per item, with the item stored in <item_name>",
+        )
+        .example("list_new names \"Ann\" \"Bo\"\nforeach name names greet")
+        .error(Type, not_a_list),
+        command(
+            "var",
+            Variables,
+            vec![required("variable_name", Name).role(Role::Defines), required("variable_value", Any)],
+            "Makes a variable, strings are interpolated right away and lists and maps are shared (Note: Variables are global and are not limited to a label)",
+        )
+        .example("var name \"World\"\nprint \"Hello {name}!\""),
+        command(
+            "require",
+            Variables,
+            vec![required("variable_name", Name)],
+            "Makes it necessary for variable <variable_name> to exist.",
+        )
+        .example("require name")
+        .error(Variable, "`<variable_name>` does not exist"),
+        command(
+            "test_lt_eq",
+            Tests,
+            vec![compared("number1", Number), compared("number2", Number), optional_destination()],
+            "Tests whether <number1> is less than or equal to <number2>",
+        )
+        .example("test_lt_eq 3 5 fits\nassert fits"),
+        command(
+            "test_gt_eq",
+            Tests,
+            vec![compared("number1", Number), compared("number2", Number), optional_destination()],
+            "Tests whether <number1> is greater than or equal to <number2>",
+        )
+        .example("test_gt_eq age 18\ncmd_eq TEMP true print \"Welcome\""),
+        command(
+            "test_lt",
+            Tests,
+            vec![compared("number1", Number), compared("number2", Number), optional_destination()],
+            "Tests whether <number1> is less than <number2>",
+        )
+        .example("test_lt i 10\ncmd_eq TEMP true jmp loop"),
+        command(
+            "test_gt",
+            Tests,
+            vec![compared("number1", Number), compared("number2", Number), optional_destination()],
+            "Tests whether <number1> is greater than <number2>",
+        )
+        .example("test_gt 2.5 2 bigger\nassert bigger"),
+        command(
+            "test_eq",
+            Tests,
+            vec![compared("value1", Any), compared("value2", Any), optional_destination()],
+            "Tests whether <value1> is equal to <value2>",
+        )
+        .example("test_eq answer 42\ncmd_eq TEMP true print \"Correct!\""),
+        command(
+            "and",
+            Bools,
+            vec![destination(), required("bool1", Bool), required("bool2", Bool)],
+            "Stores whether both <bool1> and <bool2> are true in <destination>",
+        )
+        .example("and allowed is_adult has_ticket"),
+        command(
+            "or",
+            Bools,
+            vec![destination(), required("bool1", Bool), required("bool2", Bool)],
+            "Stores whether <bool1> or <bool2> (or both) are true in <destination>",
+        )
+        .example("or day_off is_weekend is_holiday"),
+        command(
+            "xor",
+            Bools,
+            vec![destination(), required("bool1", Bool), required("bool2", Bool)],
+            "Stores whether exactly one of <bool1> and <bool2> is true in <destination>",
+        )
+        .example("xor changed old_state new_state"),
+        command(
+            "not",
+            Bools,
+            vec![destination(), required("bool", Bool)],
+            "Stores the opposite of <bool> in <destination>",
+        )
+        .example("not has_items is_empty"),
+        command(
+            "add",
+            Arithmetic,
+            vec![destination(), required("number1", Number), required("number2", Number)],
+            "Stores <number1> + <number2> in <destination>",
+        )
+        .example("add total total 1")
+        .error(ArithmeticError, overflow),
+        command(
+            "sub",
+            Arithmetic,
+            vec![destination(), required("number1", Number), required("number2", Number)],
+            "Stores <number1> - <number2> in <destination>",
+        )
+        .example("sub remaining 10 used")
+        .error(ArithmeticError, overflow),
+        command(
+            "mul",
+            Arithmetic,
+            vec![destination(), required("number1", Number), required("number2", Number)],
+            "Stores <number1> * <number2> in <destination>",
+        )
+        .example("mul area width height")
+        .error(ArithmeticError, overflow),
+        command(
+            "div",
+            Arithmetic,
+            vec![destination(), required("number1", Number), required("number2", Number)],
+            "Stores <number1> / <number2> in <destination> (Integers are rounded towards zero)",
+        )
+        .example("div half total 2")
+        .error(ArithmeticError, "`<number2>` is zero"),
+        command(
+            "mod",
+            Arithmetic,
+            vec![destination(), required("number1", Number), required("number2", Number)],
+            "Stores the remainder of <number1> / <number2> in <destination>",
+        )
+        .example("mod rest 7 2")
+        .error(ArithmeticError, "`<number2>` is zero"),
+        command(
+            "to_int",
+            Arithmetic,
+            vec![destination(), required("number", Number)],
+            "Stores <number> as an Integer (rounded towards zero) in <destination>",
+        )
+        .example("to_int whole 2.75")
+        .error(Conversion, "`<number>` is too large for an Integer"),
+        command(
+            "to_float",
+            Arithmetic,
+            vec![destination(), required("number", Number)],
+            "Stores <number> as a Float in <destination>",
+        )
+        .example("to_float ratio 3"),
+        command(
+            "typeof",
+            Types,
+            vec![destination(), required("value", Any)],
+            "Stores the type of <value> (\"Number\", \"String\", \"Bool\", \"List\", ...) in <destination>",
+        )
+        .example("typeof kind items\nprint kind"),
+        command(
+            "to_str",
+            Types,
+            vec![destination(), required("value", Any)],
+            "Stores <value> as a String in <destination>",
+        )
+        .example("to_str text 42"),
+        command(
+            "to_num",
+            Types,
+            vec![destination(), required("value", Any)],
+            "Stores <value> (e.g. the String \"42\") as a Number in <destination>, failing if it is not a number",
+        )
+        .example("to_num age \"42\"")
+        .error(Conversion, "`<value>` is not a number"),
+        command(
+            "is_num",
+            Types,
+            vec![required("value", Any), optional_destination()],
+            "Tests whether <value> is a Number or a String that to_num can convert",
+        )
+        .example("is_num input valid"),
+        command(
+            "is_str",
+            Types,
+            vec![required("value", Any), optional_destination()],
+            "Tests whether <value> is a String",
+        )
+        .example("is_str name"),
+        command(
+            "list_new",
+            Lists,
+            vec![required("list_name", Name).role(Role::Defines), variadic("values", Any)],
+            "Makes a list variable, optionally filled with <values>",
+        )
+        .example("list_new primes 2 3 5 7"),
+        command(
+            "push",
+            Lists,
+            vec![list_name(), required("value", Any)],
+            "Adds <value> to the end of a list",
+        )
+        .example("push primes 11")
+        .error(Type, not_a_list),
+        command(
+            "pop",
+            Lists,
+            vec![destination(), list_name()],
+            "Removes the last item of a list and stores it in <destination>",
+        )
+        .example("pop last primes")
+        .error(Type, not_a_list)
+        .error(Index, "the list is empty"),
+        command(
+            "get",
+            Lists,
+            vec![destination(), list_name(), required("index", Number)],
+            "Stores the item at <index> (starting from 0) in <destination>",
+        )
+        .example("get first primes 0")
+        .error(Type, not_a_list)
+        .error(Index, past_the_end),
+        command(
+            "set",
+            Lists,
+            vec![list_name(), required("index", Number), required("value", Any)],
+            "Replaces the item at <index> with <value>",
+        )
+        .example("set primes 0 1")
+        .error(Type, not_a_list)
+        .error(Index, past_the_end),
+        command(
+            "len",
+            Lists,
+            vec![destination(), list_name()],
+            "Stores the number of items of a list in <destination>",
+        )
+        .example("len count primes")
+        .error(Type, not_a_list),
+        command(
+            "insert",
+            Lists,
+            vec![list_name(), required("index", Number), required("value", Any)],
+            "Inserts <value> at <index>, shifting the following items",
+        )
+        .example("insert primes 0 1")
+        .error(Type, not_a_list)
+        .error(Index, past_the_end),
+        command(
+            "remove",
+            Lists,
+            vec![list_name(), required("index", Number)],
+            "Removes the item at <index>",
+        )
+        .example("remove primes 0")
+        .error(Type, not_a_list)
+        .error(Index, past_the_end),
+        command(
+            "sort",
+            Lists,
+            vec![list_name()],
+            "Sorts a list (Numbers by value, everything else alphabetically)",
+        )
+        .example("sort names")
+        .error(Type, not_a_list),
+        command(
+            "join",
+            Lists,
+            vec![destination(), list_name(), required("separator", String)],
+            "Joins the items of a list into a string separated by <separator>",
+        )
+        .example("join text names \", \"")
+        .error(Type, not_a_list),
+        command(
+            "map_new",
+            Maps,
+            vec![required("map_name", Name).role(Role::Defines)],
+            "Makes an empty map variable",
+        )
+            .example("map_new ages"),
+        command(
+            "map_set",
+            Maps,
+            vec![map_name(), required("key", Any), required("value", Any)],
+            "Stores <value> under <key>, replacing any previous value",
+        )
+        .example("map_set ages \"Ann\" 31")
+        .error(Type, not_a_map),
+        command(
+            "map_get",
+            Maps,
+            vec![
+                destination(),
+                map_name(),
+                required("key", Any),
+                optional("default", Any),
+            ],
+            "Stores the value under <key> in <destination>, or [default] if the key is missing",
+        )
+        .example("map_get age ages \"Bo\" 0")
+        .error(Type, not_a_map)
+        .error(Key, "`<key>` does not exist and no [default] is given"),
+        command(
+            "map_has",
+            Maps,
+            vec![map_name(), required("key", Any), optional_destination()],
+            "Tests whether <key> exists in a map, like the test_ commands",
+        )
+        .example("map_has ages \"Ann\" known")
+        .error(Type, not_a_map),
+        command(
+            "map_del",
+            Maps,
+            vec![map_name(), required("key", Any)],
+            "Removes <key> from a map",
+        )
+        .example("map_del ages \"Ann\"")
+        .error(Type, not_a_map)
+        .error(Key, "`<key>` does not exist"),
+        command(
+            "map_keys",
+            Maps,
+            vec![destination(), map_name()],
+            "Stores the sorted keys of a map as a list in <destination>",
+        )
+        .example("map_keys names ages")
+        .error(Type, not_a_map),
+        command(
+            "map_len",
+            Maps,
+            vec![destination(), map_name()],
+            "Stores the number of keys of a map in <destination>",
+        )
+        .example("map_len count ages")
+        .error(Type, not_a_map),
+        command(
+            "try",
+            Errors,
+            vec![required("label_name", Label), required("handler_label_name", Label)],
+            "Runs <label_name>, jumping to <handler_label_name> if it fails (with ERR_MSG, ERR_LINE and ERR_KIND set)",
+        )
+        .example("try load_config use_defaults"),
+        command(
+            "throw",
+            Errors,
+            vec![required("message", Any)],
+            "Fails with <message>, which can be caught using try",
+        )
+        .example("throw \"Config is missing a name\"")
+        .error(Thrown, "it is run, with `<message>` as the message"),
+        command(
+            "assert",
+            Errors,
+            vec![required("bool", Bool), optional("message", Any)],
+            "Fails (with [message] if given) unless <bool> is true",
+        )
+        .example("test_gt count 0\nassert TEMP \"The list should not be empty\"")
+        .error(Assertion, "`<bool>` is false"),
+        command(
+            "assert_eq",
+            Errors,
+            vec![compared("value1", Any), compared("value2", Any), optional("message", Any)],
+            "Fails unless <value1> is equal to <value2>, showing both values",
+        )
+        .example("assert_eq total 10")
+        .error(Assertion, "the values are not equal"),
+        command(
+            "assert_ne",
+            Errors,
+            vec![compared("value1", Any), compared("value2", Any), optional("message", Any)],
+            "Fails if <value1> is equal to <value2>, showing both values",
+        )
+        .example("assert_ne name \"\"")
+        .error(Assertion, "the values are equal"),
+        command(
+            "read_file",
+            Files,
+            vec![destination(), required("path", String)],
+            "Stores the contents of the file at <path> in <destination>",
+        )
+        .example("read_file config \"config.txt\"")
+        .error(Permission, "reading `<path>` is not allowed")
+        .error(Io, "the file cannot be read"),
+        command(
+            "read_lines",
+            Files,
+            vec![destination(), required("path", String)],
+            "Stores the lines of the file at <path> as a list in <destination>",
+        )
+        .example("read_lines lines \"names.txt\"\nforeach line lines greet")
+        .error(Permission, "reading `<path>` is not allowed")
+        .error(Io, "the file cannot be read"),
+        command(
+            "write_file",
+            Files,
+            vec![required("path", String), required("value", Any)],
+            "Writes <value> to the file at <path>, replacing its contents",
+        )
+        .example("write_file \"out.txt\" \"Done\"")
+        .error(Permission, "writing `<path>` is not allowed")
+        .error(Io, "the file cannot be written"),
+        command(
+            "append_file",
+            Files,
+            vec![required("path", String), required("value", Any)],
+            "Adds <value> to the end of the file at <path>",
+        )
+        .example("append_file \"log.txt\" \"Started\\n\"")
+        .error(Permission, "writing `<path>` is not allowed")
+        .error(Io, "the file cannot be written"),
+        command(
+            "file_exists",
+            Files,
+            vec![required("path", String), optional_destination()],
+            "Tests whether a file exists at <path>",
+        )
+        .example("file_exists \"config.txt\" has_config")
+        .error(Permission, "reading `<path>` is not allowed"),
+        command(
+            "env_get",
+            System,
+            vec![destination(), required("name", String), optional("default", Any)],
+            "Stores the environment variable <name> in <destination>, or [default] if it is not set",
+        )
+        .example("env_get home \"HOME\" \".\"")
+        .error(Permission, "environment access is not allowed")
+        .error(Variable, "`<name>` is not set and no [default] is given"),
+        command(
+            "env_set",
+            System,
+            vec![required("name", String), required("value", Any)],
+            "Sets the environment variable <name> to <value>, also for programs started later",
+        )
+        .example("env_set \"LANG\" \"C\"")
+        .error(Permission, "environment access is not allowed")
+        .error(ErrorKind::Arguments, "`<name>` is empty or contains `=`"),
+        command(
+            "exec",
+            System,
+            vec![destination(), required("program", String), variadic("args", Any)],
+            "Runs <program> with <args> and stores its output in <destination>, its errors in EXEC_STDERR and its exit code in EXEC_STATUS",
+        )
+        .example("exec version \"git\" \"describe\" \"--tags\"\nassert_eq EXEC_STATUS 0 EXEC_STDERR")
+        .error(Permission, "running programs is not allowed")
+        .error(Io, "`<program>` cannot be started"),
+        command(
+            "exec_inherit",
+            System,
+            vec![required("program", String), variadic("args", Any)],
+            "Runs <program> with <args> using the script's own input and output, storing its exit code in EXEC_STATUS",
+        )
+        .example("exec_inherit \"cargo\" \"build\" \"--release\"")
+        .error(Permission, "running programs is not allowed")
+        .error(Io, "`<program>` cannot be started"),
+        command("print", Output, vec![required("value", Any)], "Prints <value> followed by a new line")
+            .example("print \"Hello World\""),
+        command(
+            "cmt",
+            Output,
+            vec![variadic("anything", Any).role(Role::Ignored)],
+            "A comment. Ignored by the interpreter, `# ...` and `#[ ... ]#` comments are removed before running",
+        )
+        .example("cmt Greets everyone on the list"),
+    ]
+});
+
+// Every built-in command
+#[must_use]
+pub fn builtin_commands() -> &'static [CommandInfo] {
+    return &COMMANDS
+}
+
+#[must_use]
+pub fn find(name: &str) -> Option<&'static CommandInfo> {
+    return COMMANDS.iter().find(|command| command.name == name)
+}
+
+// The commands starting with `prefix`, for editor completion
+#[must_use]
+pub fn complete(prefix: &str) -> Vec<&'static CommandInfo> {
+    return COMMANDS
+        .iter()
+        .filter(|command| command.name.starts_with(prefix))
+        .collect()
+}
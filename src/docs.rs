@@ -0,0 +1,199 @@
+use crate::commands::{builtin_commands, Category, CommandInfo, Parameter};
+
+// The categories in the order their first command appears
+fn categories() -> Vec<Category> {
+    let mut categories = Vec::new();
+    for command in builtin_commands() {
+        if !categories.contains(&command.category) {
+            categories.push(command.category);
+        }
+    }
+
+    return categories
+}
+
+fn commands_in(category: Category) -> impl Iterator<Item = &'static CommandInfo> {
+    return builtin_commands()
+        .iter()
+        .filter(move |command| command.category == category)
+}
+
+fn anchor(category: Category) -> String {
+    return category.to_string().to_lowercase().replace(' ', "-")
+}
+
+// The detailed help `--h <command>` shows
+#[must_use]
+pub fn command_help(command: &CommandInfo) -> String {
+    let mut help = format!("[Command] {}\n", command.name);
+    help += &format!("[Usage] {}\n", command.usage());
+    help += &format!("{}\n", command.help);
+
+    if !command.parameters.is_empty() {
+        help += "\n[Parameters]\n";
+        for parameter in &command.parameters {
+            let name = format!("<{}>", parameter.name);
+            help += &format!("{name:<22} {}\n", describe(parameter));
+        }
+    }
+
+    if !command.examples.is_empty() {
+        help += "\n[Examples]\n";
+        for example in &command.examples {
+            help += &format!("{example}\n");
+        }
+    }
+
+    let errors = command.all_errors();
+    if !errors.is_empty() {
+        help += "\n[Errors]\n";
+        for (kind, when) in errors {
+            help += &format!("{kind}: {when}\n");
+        }
+    }
+
+    if let Some(note) = command.category.note() {
+        help += &format!("\n[Note] {note}\n");
+    }
+
+    return help
+}
+
+fn describe(parameter: &Parameter) -> String {
+    let mut description = format!("A {}", parameter.kind);
+    if parameter.optional {
+        description += ", optional";
+    } else if parameter.variadic {
+        description += ", any number of them";
+    }
+
+    return description
+}
+
+// The reference of every built-in command as Markdown
+#[must_use]
+pub fn markdown() -> String {
+    let mut doc = String::from("# script-ll-2 command reference\n\n");
+    doc += "Generated from the interpreter's command definitions using `--doc`.\n";
+    doc += "Arguments which name a variable are replaced by its value, `<...>` arguments are required and `[...]` arguments are optional.\n\n";
+
+    for category in categories() {
+        doc += &format!("- [{category}](#{})\n", anchor(category));
+    }
+
+    for category in categories() {
+        doc += &format!("\n## {category}\n");
+        if let Some(note) = category.note() {
+            doc += &format!("\n{note}\n");
+        }
+
+        for command in commands_in(category) {
+            doc += &format!("\n### `{}`\n\n", command.name);
+            doc += &format!("```\n{}\n```\n\n", command.usage());
+            doc += &format!("{}\n", command.help);
+
+            if !command.parameters.is_empty() {
+                doc += "\n| Parameter | Accepts |\n| --- | --- |\n";
+                for parameter in &command.parameters {
+                    doc += &format!("| `<{}>` | {} |\n", parameter.name, describe(parameter));
+                }
+            }
+
+            for example in &command.examples {
+                doc += &format!("\n**Example**\n\n```\n{example}\n```\n");
+            }
+
+            let errors = command.all_errors();
+            if !errors.is_empty() {
+                doc += "\n**Errors**\n\n";
+                for (kind, when) in errors {
+                    doc += &format!("- `{kind}` if {when}\n");
+                }
+            }
+        }
+    }
+
+    return doc
+}
+
+pub(crate) fn escape_html(text: &str) -> String {
+    return text
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// Turns Markdown style `code` into <code> tags
+fn inline_html(text: &str) -> String {
+    return escape_html(text)
+        .split('`')
+        .enumerate()
+        .map(|(index, part)| {
+            if index % 2 == 1 {
+                return format!("<code>{part}</code>")
+            }
+            return part.to_string()
+        })
+        .collect()
+}
+
+// The reference of every built-in command as a standalone HTML page
+#[must_use]
+pub fn html() -> String {
+    let mut doc = String::from("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>script-ll-2 command reference</title>\n</head>\n<body>\n");
+    doc += "<h1>script-ll-2 command reference</h1>\n";
+    doc +=
+        "<p>Generated from the interpreter's command definitions using <code>--doc html</code>. ";
+    doc += "Arguments which name a variable are replaced by its value, <code>&lt;...&gt;</code> arguments are required and <code>[...]</code> arguments are optional.</p>\n";
+
+    doc += "<ul>\n";
+    for category in categories() {
+        doc += &format!(
+            "<li><a href=\"#{}\">{category}</a></li>\n",
+            anchor(category)
+        );
+    }
+    doc += "</ul>\n";
+
+    for category in categories() {
+        doc += &format!("<h2 id=\"{}\">{category}</h2>\n", anchor(category));
+        if let Some(note) = category.note() {
+            doc += &format!("<p>{}</p>\n", escape_html(note));
+        }
+
+        for command in commands_in(category) {
+            doc += &format!("<h3 id=\"{0}\"><code>{0}</code></h3>\n", command.name);
+            doc += &format!("<pre>{}</pre>\n", escape_html(&command.usage()));
+            doc += &format!("<p>{}</p>\n", escape_html(&command.help));
+
+            if !command.parameters.is_empty() {
+                doc += "<table>\n<tr><th>Parameter</th><th>Accepts</th></tr>\n";
+                for parameter in &command.parameters {
+                    doc += &format!(
+                        "<tr><td><code>&lt;{}&gt;</code></td><td>{}</td></tr>\n",
+                        parameter.name,
+                        describe(parameter)
+                    );
+                }
+                doc += "</table>\n";
+            }
+
+            for example in &command.examples {
+                doc += &format!("<h4>Example</h4>\n<pre>{}</pre>\n", escape_html(example));
+            }
+
+            let errors = command.all_errors();
+            if !errors.is_empty() {
+                doc += "<h4>Errors</h4>\n<ul>\n";
+                for (kind, when) in errors {
+                    doc += &format!("<li><code>{kind}</code> if {}</li>\n", inline_html(&when));
+                }
+                doc += "</ul>\n";
+            }
+        }
+    }
+
+    doc += "</body>\n</html>\n";
+    return doc
+}
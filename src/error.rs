@@ -0,0 +1,83 @@
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ErrorKind {
+    Arguments,
+    Type,
+    Variable,
+    Label,
+    Index,
+    Key,
+    Arithmetic,
+    Conversion,
+    Syntax,
+    Thrown,
+    Assertion,
+    Io,
+    Permission,
+    InstructionLimit,
+    TimeLimit,
+    CallDepthLimit,
+    MemoryLimit,
+}
+
+impl ErrorKind {
+    // Limits stop the whole script, `try` cannot catch them
+    #[must_use]
+    pub const fn is_limit(self) -> bool {
+        return matches!(
+            self,
+            Self::InstructionLimit | Self::TimeLimit | Self::CallDepthLimit | Self::MemoryLimit
+        )
+    }
+}
+
+// The names scripts see in `ERR_KIND`
+impl std::fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Arguments => write!(f, "ArgumentError"),
+            Self::Type => write!(f, "TypeError"),
+            Self::Variable => write!(f, "VariableError"),
+            Self::Label => write!(f, "LabelError"),
+            Self::Index => write!(f, "IndexError"),
+            Self::Key => write!(f, "KeyError"),
+            Self::Arithmetic => write!(f, "ArithmeticError"),
+            Self::Conversion => write!(f, "ConversionError"),
+            Self::Syntax => write!(f, "SyntaxError"),
+            Self::Thrown => write!(f, "ThrownError"),
+            Self::Assertion => write!(f, "AssertionError"),
+            Self::Io => write!(f, "IoError"),
+            Self::Permission => write!(f, "PermissionError"),
+            Self::InstructionLimit => write!(f, "InstructionLimitError"),
+            Self::TimeLimit => write!(f, "TimeLimitError"),
+            Self::CallDepthLimit => write!(f, "CallDepthLimitError"),
+            Self::MemoryLimit => write!(f, "MemoryLimitError"),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ScriptError {
+    pub kind: ErrorKind,
+    pub message: String,
+    pub line_number: usize,
+    pub line_code: String,
+    // Extra lines such as `[Usage] ...` or `[Help] ...`
+    pub notes: Vec<String>,
+}
+
+impl std::fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "[Error] {}", self.message)?;
+
+        // Errors found before the script runs may not belong to a line
+        if !self.line_code.is_empty() {
+            write!(f, "\n[Code]\n{} | {}", self.line_number, self.line_code)?;
+        }
+
+        for note in &self.notes {
+            write!(f, "\n{note}")?;
+        }
+
+        Ok(())
+    }
+}
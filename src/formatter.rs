@@ -0,0 +1,94 @@
+use crate::error::ScriptError;
+use crate::lexer::{self, TokenKind};
+
+const INDENTATION: &str = "    ";
+
+// A part of a line as it is written in the source
+enum Piece {
+    Token(String),
+    Comment(String),
+}
+
+// Splits code into lines of tokens and comments, keeping each of them exactly as written.
+// Strings and comments spanning several lines belong to the line they start in
+fn split_lines(code: &str) -> Vec<Vec<Piece>> {
+    let mut lines: Vec<Vec<Piece>> = Vec::new();
+    let mut line: Vec<Piece> = Vec::new();
+
+    for token in lexer::tokens(code) {
+        match token.kind {
+            TokenKind::Newline => lines.push(std::mem::take(&mut line)),
+            TokenKind::Space => {}
+            TokenKind::Comment => line.push(Piece::Comment(token.text.trim_end().to_string())),
+            TokenKind::String | TokenKind::Word => line.push(Piece::Token(token.text)),
+        }
+    }
+    lines.push(line);
+
+    return lines
+}
+
+fn is_label(line: &[Piece]) -> bool {
+    return matches!(line.first(), Some(Piece::Token(token)) if token == "label")
+}
+
+fn is_comment(line: &[Piece]) -> bool {
+    return !line.is_empty() && line.iter().all(|piece| matches!(piece, Piece::Comment(_)))
+}
+
+// Rewrites a script canonically: labels flush-left with a blank line before them, their code
+// indented by four spaces and single spaces between arguments. Comments and the content of strings
+// are kept as they are
+pub fn format(code: &str) -> Result<String, ScriptError> {
+    // Code which does not lex is left alone, its strings and comments cannot be told apart
+    lexer::lex(code.to_string())?;
+
+    let lines = split_lines(code.replace('\r', "").trim());
+    let mut formatted: Vec<String> = Vec::new();
+    let mut in_label = false;
+    let mut blank_line = false;
+    // Whether the last line was a label or a comment above one
+    let mut after_label = false;
+
+    for (index, line) in lines.iter().enumerate() {
+        if line.is_empty() {
+            blank_line = true;
+            continue;
+        }
+
+        let text = line
+            .iter()
+            .map(|piece| match piece {
+                Piece::Token(text) | Piece::Comment(text) => return text.as_str(),
+            })
+            .collect::<Vec<&str>>()
+            .join(" ");
+
+        // Comments right above a label belong to it
+        let starts_label = is_label(line)
+            || (is_comment(line)
+                && lines[index + 1..]
+                    .iter()
+                    .find(|line| !is_comment(line))
+                    .is_some_and(|line| is_label(line)));
+
+        if starts_label {
+            if !formatted.is_empty() && !after_label {
+                formatted.push(String::new());
+            }
+            formatted.push(text);
+            in_label |= is_label(line);
+        } else {
+            if blank_line && !after_label {
+                formatted.push(String::new());
+            }
+            let indentation = if in_label { INDENTATION } else { "" };
+            formatted.push(format!("{indentation}{text}"));
+        }
+
+        after_label = starts_label;
+        blank_line = false;
+    }
+
+    return Ok(format!("{}\n", formatted.join("\n")))
+}
@@ -0,0 +1,245 @@
+use serde_json::json;
+
+use crate::ast::{self, parse_number};
+use crate::commands::{self, builtin_commands, ArgumentKind, Category, CommandInfo, Role};
+use crate::docs::escape_html;
+use crate::error::ScriptError;
+use crate::lexer::{self, TokenKind};
+
+// Colours for the classes used by `html`, printed by `--highlight css`
+pub const STYLESHEET: &str = "pre.script-ll { background: #f6f8fa; padding: 1em; }
+.ll-command { color: #8250df; font-weight: bold; }
+.ll-label { color: #0550ae; }
+.ll-string { color: #0a3069; }
+.ll-number, .ll-bool { color: #953800; }
+.ll-variable { color: #24292f; font-style: italic; }
+.ll-comment { color: #6e7781; }
+";
+
+fn span(class: &str, text: &str) -> String {
+    return format!("<span class=\"{class}\">{}</span>", escape_html(text))
+}
+
+// A string with its `{name}` placeholders marked as variables
+fn string_html(string: &str) -> String {
+    let mut html = String::new();
+    let mut rest = string;
+
+    while let Some(start) = rest.find('{') {
+        let escaped = rest[start..].starts_with("{{");
+        let end = rest[start..].find('}').map(|end| start + end + 1);
+        match end {
+            Some(end) if !escaped => {
+                html += &escape_html(&rest[..start]);
+                html += &span("ll-variable", &rest[start..end]);
+                rest = &rest[end..];
+            }
+            _ => {
+                let end = start + if escaped { 2 } else { 1 };
+                html += &escape_html(&rest[..end]);
+                rest = &rest[end..];
+            }
+        }
+    }
+    html += &escape_html(rest);
+
+    return format!("<span class=\"ll-string\">{html}</span>")
+}
+
+// What a word is, from its place in the line: a command, a label or a value
+fn word_class(
+    word: &str,
+    index: &mut usize,
+    command: &mut Option<&'static CommandInfo>,
+) -> Option<&'static str> {
+    let parameter = command.and_then(|info| {
+        return index.checked_sub(1).and_then(|index| {
+            return info.parameters.get(index).or_else(|| {
+                info.parameters
+                    .last()
+                    .filter(|parameter| parameter.variadic)
+            });
+        })
+    });
+
+    // `cmt` is a comment too, even though it is a command
+    if parameter.is_some_and(|parameter| parameter.role == Role::Ignored) {
+        return Some("ll-comment")
+    }
+
+    // The arguments after the command run by e.g. `cmd_eq` belong to that command
+    if *index == 0 || parameter.is_some_and(|parameter| parameter.kind == ArgumentKind::Command) {
+        *command = commands::find(word);
+        *index = 0;
+        return command.map(|_| "ll-command")
+    } else if parameter.is_some_and(|parameter| parameter.kind == ArgumentKind::Label) {
+        return Some("ll-label")
+    } else if parse_number(word).is_some() {
+        return Some("ll-number")
+    } else if word == "true" || word == "false" {
+        return Some("ll-bool")
+    } else if ast::is_identifier(word) {
+        return Some("ll-variable")
+    } else {
+        return None
+    }
+}
+
+// Highlights a script as HTML, for example to show it in documentation. Commands, labels, strings,
+// numbers, bools, variables and comments get their own `ll-*` class, see `STYLESHEET`
+pub fn html(code: &str) -> Result<String, ScriptError> {
+    // Code which does not lex is not highlighted, it would be shown wrongly
+    lexer::lex(code.to_string())?;
+
+    let mut html = String::from("<pre class=\"script-ll\"><code>");
+    let mut command: Option<&'static CommandInfo> = None;
+    // Which word of its line the next word is
+    let mut index = 0;
+
+    for token in lexer::tokens(code.replace('\r', "").trim_end()) {
+        let text = token.text;
+        match token.kind {
+            TokenKind::Space | TokenKind::Newline | TokenKind::Comment => {
+                if text.contains('\n') {
+                    command = None;
+                    index = 0;
+                }
+                html += &match token.kind {
+                    TokenKind::Comment => span("ll-comment", &text),
+                    _ => escape_html(&text),
+                };
+                continue;
+            }
+
+            TokenKind::String => html += &string_html(&text),
+
+            TokenKind::Word => match word_class(&text, &mut index, &mut command) {
+                Some(class) => html += &span(class, &text),
+                None => html += &escape_html(&text),
+            },
+        }
+        index += 1;
+    }
+
+    html += "</code></pre>\n";
+    return Ok(html)
+}
+
+// A TextMate grammar for `.ll` scripts, generated from the built-in commands. Editors such as
+// VS Code and Sublime Text, and highlighters using their grammars, can load it
+#[must_use]
+pub fn textmate_grammar() -> String {
+    let mut names = builtin_commands()
+        .iter()
+        .map(|command| command.name.clone())
+        .collect::<Vec<String>>();
+    // Longer names first, so that `test_gt_eq` is not read as `test_gt`
+    names.sort_by_key(|name| std::cmp::Reverse(name.len()));
+
+    let keywords = builtin_commands()
+        .iter()
+        .filter(|command| command.category == Category::ControlFlow)
+        .map(|command| command.name.clone())
+        .collect::<Vec<String>>();
+
+    // Commands which take labels, e.g. `(try)\s+(\S+)\s+(\S+)` for `try <label> <handler>`
+    let label_arguments = builtin_commands()
+        .iter()
+        .filter_map(|command| {
+            let last_label = command
+                .parameters
+                .iter()
+                .rposition(|parameter| parameter.kind == ArgumentKind::Label)?;
+
+            let mut captures = serde_json::Map::new();
+            captures.insert("1".to_string(), json!({"name": "keyword.control.script-ll"}));
+            for (index, parameter) in command.parameters[..=last_label].iter().enumerate() {
+                if parameter.kind == ArgumentKind::Label {
+                    let name = if parameter.role == Role::Defines {
+                        "entity.name.function.label.script-ll"
+                    } else {
+                        "entity.name.label.script-ll"
+                    };
+                    captures.insert((index + 2).to_string(), json!({"name": name}));
+                }
+            }
+
+            return Some(json!({
+                "match": format!("^\\s*({}){}(?=\\s|$)", command.name, "\\s+(\\S+)".repeat(last_label + 1)),
+                "captures": captures,
+            }))
+        })
+        .collect::<Vec<serde_json::Value>>();
+
+    let grammar = json!({
+        "$schema": "https://raw.githubusercontent.com/martinring/tmlanguage/master/tmlanguage.json",
+        "name": "script-ll",
+        "scopeName": "source.script-ll",
+        "fileTypes": ["ll"],
+        "patterns": [
+            {"include": "#comments"},
+            {"include": "#strings"},
+            {"include": "#labels"},
+            {"include": "#commands"},
+            {"include": "#values"},
+        ],
+        "repository": {
+            "comments": {
+                "patterns": [
+                    {"name": "comment.block.script-ll", "begin": "#\\[", "end": "\\]#"},
+                    {"name": "comment.line.number-sign.script-ll", "match": "#.*$"},
+                ],
+            },
+            "strings": {
+                "patterns": [
+                    {
+                        "name": "string.quoted.triple.script-ll",
+                        "begin": "\"\"\"",
+                        "end": "\"\"\"",
+                        "patterns": [{"include": "#placeholders"}],
+                    },
+                    {
+                        "name": "string.quoted.double.script-ll",
+                        "begin": "\"",
+                        "end": "\"|$",
+                        "patterns": [{"include": "#placeholders"}],
+                    },
+                ],
+            },
+            "placeholders": {
+                "patterns": [
+                    {"name": "constant.character.escape.script-ll", "match": "\\{\\{|\\}\\}"},
+                    {"name": "variable.other.placeholder.script-ll", "match": "\\{[^{}\"]*\\}"},
+                ],
+            },
+            "labels": {"patterns": label_arguments},
+            "commands": {
+                "patterns": [
+                    {
+                        "name": "keyword.control.script-ll",
+                        "match": format!("(?<=^|\\s)({})(?=\\s|$)", keywords.join("|")),
+                    },
+                    {
+                        "name": "support.function.script-ll",
+                        "match": format!("(?<=^|\\s)({})(?=\\s|$)", names.join("|")),
+                    },
+                ],
+            },
+            "values": {
+                "patterns": [
+                    {"name": "constant.language.bool.script-ll", "match": "(?<![\\w.])(true|false)(?![\\w.])"},
+                    {
+                        "name": "constant.numeric.script-ll",
+                        "match": "(?<![\\w.])-?(0[xX][0-9a-fA-F]+(_[0-9a-fA-F]+)*|0[bB][01]+(_[01]+)*|[0-9]+(_[0-9]+)*(\\.[0-9]+(_[0-9]+)*)?([eE][+-]?[0-9]+)?)(?![\\w.])",
+                    },
+                    {"name": "variable.other.script-ll", "match": "(?<![\\w.])[a-zA-Z_][a-zA-Z0-9_]*(?![\\w.])"},
+                ],
+            },
+        },
+    });
+
+    return format!(
+        "{}\n",
+        serde_json::to_string_pretty(&grammar).unwrap_or_default()
+    );
+}
@@ -0,0 +1,137 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::rc::Rc;
+
+use crate::ast::{to_string_literal, Number, Value};
+use crate::commands::CommandInfo;
+pub use crate::commands::{ArgumentKind, Parameter};
+use crate::error::{ErrorKind, ScriptError};
+use crate::interpreter::Interpreter;
+use crate::permissions::Capability;
+
+// An argument after it was looked up and checked against its parameter
+#[derive(PartialEq, Clone, Debug)]
+pub enum Argument {
+    Name(String),
+    String(String),
+    Number(Number),
+    Bool(bool),
+    List(Vec<Value>),
+    Map(BTreeMap<String, Value>),
+}
+
+impl Argument {
+    #[must_use]
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::Name(text) | Self::String(text) => return Some(text),
+            _ => return None,
+        }
+    }
+
+    #[must_use]
+    pub const fn as_number(&self) -> Option<Number> {
+        match self {
+            Self::Number(number) => return Some(*number),
+            _ => return None,
+        }
+    }
+
+    #[must_use]
+    pub const fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(bool) => return Some(*bool),
+            _ => return None,
+        }
+    }
+
+    #[must_use]
+    pub const fn as_list(&self) -> Option<&Vec<Value>> {
+        match self {
+            Self::List(items) => return Some(items),
+            _ => return None,
+        }
+    }
+
+    #[must_use]
+    pub const fn as_map(&self) -> Option<&BTreeMap<String, Value>> {
+        match self {
+            Self::Map(entries) => return Some(entries),
+            _ => return None,
+        }
+    }
+
+    // The value a script would see, e.g. to store the argument in a variable
+    #[must_use]
+    pub fn into_value(self) -> Value {
+        match self {
+            Self::Name(name) => return Value::Literal(name),
+            Self::String(content) => return Value::Literal(to_string_literal(&content)),
+            Self::Number(number) => return Value::Literal(number.to_string()),
+            Self::Bool(bool) => return Value::Literal(bool.to_string()),
+            Self::List(items) => return Value::List(items),
+            Self::Map(entries) => return Value::Map(entries),
+        }
+    }
+}
+
+pub type HostFunction = dyn Fn(&mut Context, &[Argument]) -> Result<(), ScriptError>;
+
+// A native command registered by the program embedding the interpreter
+#[derive(Clone)]
+pub struct HostCommand {
+    pub info: CommandInfo,
+    pub function: Rc<HostFunction>,
+}
+
+// The handle host commands use to reach the script while they run
+pub struct Context<'a> {
+    pub(crate) interpreter: &'a mut Interpreter,
+    pub(crate) command: String,
+}
+
+impl Context<'_> {
+    // The value of variable `name`, errors if it does not exist
+    pub fn get(&self, name: &str) -> Result<Argument, ScriptError> {
+        let value = self.interpreter.get_value(name.to_string())?;
+        return self.interpreter.to_argument(value, name, ArgumentKind::Any)
+    }
+
+    pub fn set(&mut self, name: &str, value: Value) {
+        self.interpreter.set_variable(name.to_string(), value);
+    }
+
+    pub fn set_string(&mut self, name: &str, content: &str) {
+        self.set(name, Value::Literal(to_string_literal(content)));
+    }
+
+    pub fn set_number(&mut self, name: &str, number: Number) {
+        self.set(name, Value::Literal(number.to_string()));
+    }
+
+    pub fn set_bool(&mut self, name: &str, bool: bool) {
+        self.set(name, Value::Literal(bool.to_string()));
+    }
+
+    // Prints a line the same way `print` does
+    pub fn print(&mut self, content: &str) -> Result<(), ScriptError> {
+        return self.interpreter.print(content)
+    }
+
+    // Fails with the same error a built-in command would give, e.g. `--allow-net` for `Net`
+    pub fn check_permission(
+        &self,
+        capability: Capability,
+        path: Option<&Path>,
+    ) -> Result<(), ScriptError> {
+        return self
+            .interpreter
+            .check_permission(&self.command, capability, path)
+    }
+
+    // An error pointing at the line which called the command
+    #[must_use]
+    pub fn error(&self, kind: ErrorKind, message: String, notes: Vec<String>) -> ScriptError {
+        return self.interpreter.error(kind, message, notes)
+    }
+}
@@ -1,25 +1,59 @@
-use std::{collections::HashMap, process};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
+    env, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process,
+    rc::Rc,
+    time::Instant,
+};
 
-use regex::Regex;
-
-use crate::ast::{self, get_code_from, has_label, Label};
+use crate::ast::{
+    self, get_code_from, has_label, parse_number, to_string_literal, Label, Number, Value,
+};
+use crate::commands::{self, Category, CommandInfo};
+use crate::error::{ErrorKind, ScriptError};
+use crate::host::{Argument, ArgumentKind, Context, HostCommand, Parameter};
+use crate::limits::Limits;
+use crate::permissions::{Capability, Permissions};
 
 pub struct Interpreter {
     labels: Vec<Label>,
-    variables: HashMap<String, String>,
+    variables: HashMap<String, Value>,
     current_line_number: usize,
     current_line_code: String,
+    // Where `print` writes to, stdout unless changed with `set_output`
+    output: Box<dyn Write>,
+    // File paths used by scripts are relative to this directory
+    script_dir: PathBuf,
+    // The command line arguments after the script, available as ARGC and ARGV
+    args: Vec<String>,
+    permissions: Permissions,
+    limits: Limits,
+    current_label: String,
+    instructions: u64,
+    call_depth: usize,
+    started_at: Option<Instant>,
+    host_commands: HashMap<String, HostCommand>,
 }
 
-fn get_type(token: String) -> ast::Types {
-    let number_re = Regex::new(r"^[0-9]+(\.[0-9]+)?$").unwrap();
-    let identifier_re = Regex::new(r"^[a-zA-Z_][a-zA-Z0-9_]*$").unwrap();
+// The `[Usage]` note of a built-in command
+fn usage_note(command: &str) -> String {
+    match commands::find(command) {
+        Some(info) => return format!("[Usage] {}", info.usage()),
+        None => return format!("[Usage] {command}"),
+    }
+}
 
+fn get_type(token: String) -> ast::Types {
     if token.starts_with('\"') && token.ends_with('\"') {
         return ast::Types::String
-    } else if number_re.is_match(&token) {
+    } else if parse_number(&token).is_some() {
         return ast::Types::Number;
-    } else if identifier_re.is_match(&token) {
+    } else if token == "true" || token == "false" {
+        return ast::Types::Bool;
+    } else if ast::is_identifier(&token) {
         return ast::Types::Identifier;
     } else {
         return ast::Types::Unknown;
@@ -36,6 +70,41 @@ fn get_type(token: String) -> ast::Types {
 //     }
 // }
 
+fn get_value_type(value: &Value) -> ast::Types {
+    match value {
+        Value::Literal(token) => return get_type(token.clone()),
+        Value::List(_) => return ast::Types::List,
+        Value::Map(_) => return ast::Types::Map,
+    }
+}
+
+// Tests wrote 1 and 0 before there were bools, so `cmd_eq TEMP 1` still works
+fn bool_as_number(value: &str) -> &str {
+    match value {
+        "true" => return "1",
+        "false" => return "0",
+        _ => return value,
+    }
+}
+
+// Where a command stores its result, the argument at `index` or TEMP if it is left out
+fn destination(arguments: &[String], index: usize) -> String {
+    return arguments
+        .get(index)
+        .cloned()
+        .unwrap_or_else(|| String::from("TEMP"))
+}
+
+// Numbers are equal by value, so `16` equals `0x10` and `16.0`, and `true` equals `1`
+fn is_equal(value1: &str, value2: &str) -> bool {
+    let (value1, value2) = (bool_as_number(value1), bool_as_number(value2));
+
+    match (parse_number(value1), parse_number(value2)) {
+        (Some(number1), Some(number2)) => return number1 == number2,
+        _ => return value1 == value2,
+    }
+}
+
 fn rem_first_and_last(value: &str) -> &str {
     let mut chars = value.chars();
     chars.next();
@@ -43,14 +112,6 @@ fn rem_first_and_last(value: &str) -> &str {
     return chars.as_str();
 }
 
-fn get_string_content(string: String) -> String {
-    if string.starts_with('\"') && string.ends_with('\"') {
-        return rem_first_and_last(&string).to_string().replace("\\n", "\n");
-    } else {
-        return string
-    }
-}
-
 impl Interpreter {
     #[must_use]
     pub fn new(labels: Vec<Label>) -> Self {
@@ -59,430 +120,1464 @@ impl Interpreter {
             variables: HashMap::new(),
             current_line_code: String::new(),
             current_line_number: 0,
+            output: Box::new(io::stdout()),
+            script_dir: PathBuf::new(),
+            args: Vec::new(),
+            permissions: Permissions::all(),
+            limits: Limits::default(),
+            current_label: String::new(),
+            instructions: 0,
+            call_depth: 0,
+            started_at: None,
+            host_commands: HashMap::new(),
         }
     }
 
-    fn get_variable(&self, variable_name: String) -> (String, ast::Types) {
+    pub fn set_output(&mut self, output: Box<dyn Write>) {
+        self.output = output;
+    }
+
+    pub fn set_script_dir(&mut self, script_dir: PathBuf) {
+        self.script_dir = script_dir;
+    }
+
+    pub fn set_args(&mut self, args: Vec<String>) {
+        self.args = args;
+    }
+
+    pub fn set_permissions(&mut self, permissions: Permissions) {
+        self.permissions = permissions;
+    }
+
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.limits = limits;
+    }
+
+    // Adds a native command, which takes precedence over a built-in command of the same name.
+    // The arguments are checked against `parameters` before `function` runs.
+    pub fn register(
+        &mut self,
+        name: &str,
+        parameters: Vec<Parameter>,
+        function: impl Fn(&mut Context, &[Argument]) -> Result<(), ScriptError> + 'static,
+    ) {
+        self.host_commands.insert(
+            name.to_string(),
+            HostCommand {
+                info: CommandInfo {
+                    name: name.to_string(),
+                    category: Category::Host,
+                    parameters,
+                    help: String::new(),
+                    examples: Vec::new(),
+                    errors: Vec::new(),
+                },
+                function: Rc::new(function),
+            },
+        );
+    }
+
+    // Points the error at the line currently being interpreted
+    pub(crate) fn error(
+        &self,
+        kind: ErrorKind,
+        message: String,
+        notes: Vec<String>,
+    ) -> ScriptError {
+        return ScriptError {
+            kind,
+            message,
+            line_number: self.current_line_number,
+            line_code: self.current_line_code.clone(),
+            notes,
+        }
+    }
+
+    fn get_variable(&self, variable_name: String) -> Result<(String, ast::Types), ScriptError> {
         match self.variables.clone().get(&variable_name) {
-            Some(value) => {
+            Some(value @ (Value::List(_) | Value::Map(_))) => {
+                return Ok((value.to_string(), get_value_type(value)))
+            }
+
+            Some(Value::Literal(value)) => {
                 let value = (*value).to_string();
                 let value_type = get_type(value.clone());
                 if value_type == ast::Types::Identifier {
-                    let value = self.get_variable(value);
-                    return (value.0, value.1)
+                    let value = self.get_variable(value)?;
+                    return Ok((value.0, value.1))
                 } else if value_type == ast::Types::String {
-                    return (get_string_content(value), value_type);
+                    return Ok((self.get_string_content(value)?, value_type));
                 } else {
-                    return (value, value_type);
+                    return Ok((value, value_type));
                 }
             }
 
             None => {
-                println!("[Error] Variable `{variable_name}` does not exist.");
-                println!("[Code]");
-                println!("{} | {}", self.current_line_number, self.current_line_code);
-                process::exit(1);
+                return Err(self.error(
+                    ErrorKind::Variable,
+                    format!("Variable `{variable_name}` does not exist."),
+                    Vec::new(),
+                ));
+            }
+        }
+    }
+
+    // Like `get_variable`, but keeps the value intact so lists can be copied around
+    pub(crate) fn get_value(&self, variable_name: String) -> Result<Value, ScriptError> {
+        match self.variables.get(&variable_name) {
+            Some(Value::Literal(value)) if get_type(value.clone()) == ast::Types::Identifier => {
+                return self.get_value(value.clone())
+            }
+
+            Some(Value::Literal(value)) if get_type(value.clone()) == ast::Types::String => {
+                return Ok(Value::Literal(to_string_literal(
+                    &self.get_string_content(value.clone())?,
+                )))
+            }
+
+            Some(value) => return Ok(value.clone()),
+
+            None => {
+                return Err(self.error(
+                    ErrorKind::Variable,
+                    format!("Variable `{variable_name}` does not exist."),
+                    Vec::new(),
+                ));
+            }
+        }
+    }
+
+    // Turns an argument into a value which no longer depends on other variables
+    fn evaluate(&self, token: String) -> Result<Value, ScriptError> {
+        match get_type(token.clone()) {
+            ast::Types::Identifier => return self.get_value(token),
+            ast::Types::String => {
+                return Ok(Value::Literal(to_string_literal(
+                    &self.get_string_content(token)?,
+                )))
+            }
+            _ => return Ok(Value::Literal(token)),
+        }
+    }
+
+    // Follows `var a b` style references to the variable actually holding the value
+    fn resolve_variable_name(&self, variable_name: String) -> String {
+        match self.variables.get(&variable_name) {
+            Some(Value::Literal(value)) if get_type(value.clone()) == ast::Types::Identifier => {
+                return self.resolve_variable_name(value.clone())
+            }
+
+            _ => return variable_name,
+        }
+    }
+
+    fn get_list(&self, list_name: String) -> Result<Vec<Value>, ScriptError> {
+        match self.get_value(list_name.clone())? {
+            Value::List(items) => return Ok(items),
+
+            value => {
+                return Err(self.error(
+                    ErrorKind::Type,
+                    format!(
+                        "Expected `{list_name}` to be a List, not a {}",
+                        get_value_type(&value)
+                    ),
+                    vec![format!("[Help] Create a list using `list_new {list_name}`")],
+                ));
+            }
+        }
+    }
+
+    fn get_list_mut(&mut self, list_name: String) -> Result<&mut Vec<Value>, ScriptError> {
+        // Reports the error if the variable is missing or is not a list
+        self.get_list(list_name.clone())?;
+
+        let list_name = self.resolve_variable_name(list_name);
+        match self.variables.get_mut(&list_name) {
+            Some(Value::List(items)) => return Ok(items),
+            _ => unreachable!(),
+        }
+    }
+
+    fn get_map(&self, map_name: String) -> Result<BTreeMap<String, Value>, ScriptError> {
+        match self.get_value(map_name.clone())? {
+            Value::Map(entries) => return Ok(entries),
+
+            value => {
+                return Err(self.error(
+                    ErrorKind::Type,
+                    format!(
+                        "Expected `{map_name}` to be a Map, not a {}",
+                        get_value_type(&value)
+                    ),
+                    vec![format!("[Help] Create a map using `map_new {map_name}`")],
+                ));
+            }
+        }
+    }
+
+    fn get_map_mut(
+        &mut self,
+        map_name: String,
+    ) -> Result<&mut BTreeMap<String, Value>, ScriptError> {
+        // Reports the error if the variable is missing or is not a map
+        self.get_map(map_name.clone())?;
+
+        let map_name = self.resolve_variable_name(map_name);
+        match self.variables.get_mut(&map_name) {
+            Some(Value::Map(entries)) => return Ok(entries),
+            _ => unreachable!(),
+        }
+    }
+
+    // The raw value of an argument, looked up first if it names a variable
+    fn resolve(&self, token: String) -> Result<String, ScriptError> {
+        if get_type(token.clone()) == ast::Types::Identifier {
+            return Ok(self.get_variable(token)?.0)
+        } else {
+            return Ok(token)
+        }
+    }
+
+    // The value of an argument as plain text, without the quotes of string literals
+    fn get_content(&self, token: String) -> Result<String, ScriptError> {
+        if get_type(token.clone()) == ast::Types::Identifier {
+            return Ok(self.get_variable(token)?.0)
+        } else {
+            return self.get_string_content(token)
+        }
+    }
+
+    // The type of an argument, or of the value of the variable it names
+    fn value_type(&self, token: String) -> Result<ast::Types, ScriptError> {
+        if get_type(token.clone()) == ast::Types::Identifier {
+            return Ok(self.get_variable(token)?.1)
+        } else {
+            return Ok(get_type(token))
+        }
+    }
+
+    // Whether two arguments are equal, comparing strings by their content. Every comparison command
+    // goes through this, so that they agree
+    fn equals(&self, token1: String, token2: String) -> Result<bool, ScriptError> {
+        let value1 = self.get_content(token1)?;
+        let value2 = self.get_content(token2)?;
+
+        return Ok(is_equal(&value1, &value2))
+    }
+
+    fn get_number(&self, token: String) -> Result<Number, ScriptError> {
+        let value = self.resolve(token)?;
+
+        match parse_number(&value) {
+            Some(number) => return Ok(number),
+
+            None => {
+                return Err(self.error(
+                    ErrorKind::Type,
+                    format!(
+                        "Expected the value to be a Number, not a {}",
+                        get_type(value)
+                    ),
+                    Vec::new(),
+                ));
+            }
+        }
+    }
+
+    fn get_bool(&self, token: String) -> Result<bool, ScriptError> {
+        let value = self.resolve(token)?;
+
+        if get_type(value.clone()) != ast::Types::Bool {
+            return Err(self.error(
+                ErrorKind::Type,
+                format!("Expected the value to be a Bool, not a {}", get_type(value)),
+                vec!["[Note] Only `true` and `false` are Bools".to_string()],
+            ));
+        }
+
+        return Ok(value == "true")
+    }
+
+    fn get_path(&self, token: String) -> Result<PathBuf, ScriptError> {
+        return Ok(self.script_dir.join(self.get_content(token)?))
+    }
+
+    // `path` is only needed for the `Read` and `Write` capabilities
+    pub(crate) fn check_permission(
+        &self,
+        command: &str,
+        capability: Capability,
+        path: Option<&Path>,
+    ) -> Result<(), ScriptError> {
+        if self.permissions.allows(capability, path) {
+            return Ok(());
+        }
+
+        match path {
+            // The flag takes paths relative to where the interpreter was started, not the script
+            Some(path) => {
+                return Err(self.error(
+                    ErrorKind::Permission,
+                    format!(
+                        "Permission denied: `{command}` needs {capability} access to `{}`",
+                        self.display_path(path)
+                    ),
+                    vec![format!(
+                        "[Help] Allow it using `--allow-{capability}={}`",
+                        path.display()
+                    )],
+                ));
+            }
+
+            None => {
+                return Err(self.error(
+                    ErrorKind::Permission,
+                    format!("Permission denied: `{command}` needs {capability} access"),
+                    vec![format!("[Help] Allow it using `--allow-{capability}`")],
+                ));
+            }
+        }
+    }
+
+    // Shows paths the way the script wrote them
+    fn display_path(&self, path: &Path) -> String {
+        return path
+            .strip_prefix(&self.script_dir)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    }
+
+    fn read_file(&self, path: &Path) -> Result<String, ScriptError> {
+        match fs::read_to_string(path) {
+            Ok(content) => return Ok(content),
+
+            Err(error) => {
+                return Err(self.error(
+                    ErrorKind::Io,
+                    format!("Could not read file `{}`", self.display_path(path)),
+                    vec![format!("[Reason] {error}")],
+                ));
+            }
+        }
+    }
+
+    // `max_index` is inclusive, so `insert` can pass the length of the list
+    fn get_index(&self, token: String, max_index: usize) -> Result<usize, ScriptError> {
+        let index = self.resolve(token)?;
+
+        let parsed_index = match parse_number(&index) {
+            Some(Number::Integer(integer)) => usize::try_from(integer).ok(),
+            _ => None,
+        };
+
+        match parsed_index {
+            Some(index) if index <= max_index => return Ok(index),
+
+            Some(index) => {
+                return Err(self.error(
+                    ErrorKind::Index,
+                    format!("Index {index} is out of bounds."),
+                    vec![format!(
+                        "[Note] The index must be between 0 and {max_index}"
+                    )],
+                ));
+            }
+
+            None => {
+                return Err(self.error(
+                    ErrorKind::Type,
+                    format!(
+                        "Expected the index to be a non-negative Integer, not `{index}` ({})",
+                        get_type(index.clone())
+                    ),
+                    Vec::new(),
+                ));
+            }
+        }
+    }
+
+    pub(crate) fn set_variable(&mut self, name: String, value: Value) {
+        self.variables.insert(name, value);
+    }
+
+    pub(crate) fn print(&mut self, content: &str) -> Result<(), ScriptError> {
+        if let Err(error) = writeln!(self.output, "{content}") {
+            return Err(self.error(
+                ErrorKind::Io,
+                "Could not print the value.".to_string(),
+                vec![format!("[Reason] {error}")],
+            ));
+        }
+
+        Ok(())
+    }
+
+    // Checks an evaluated value against the kind a host command parameter expects
+    pub(crate) fn to_argument(
+        &self,
+        value: Value,
+        name: &str,
+        kind: ArgumentKind,
+    ) -> Result<Argument, ScriptError> {
+        let argument = match value {
+            Value::List(items) => Argument::List(items),
+            Value::Map(entries) => Argument::Map(entries),
+            Value::Literal(token) => match get_type(token.clone()) {
+                ast::Types::String => Argument::String(self.get_string_content(token)?),
+                ast::Types::Number => Argument::Number(parse_number(&token).unwrap()),
+                ast::Types::Bool => Argument::Bool(token == "true"),
+                _ => Argument::String(token),
+            },
+        };
+
+        let matches = match kind {
+            ArgumentKind::Any => true,
+            ArgumentKind::String => matches!(argument, Argument::String(_)),
+            ArgumentKind::Number => matches!(argument, Argument::Number(_)),
+            ArgumentKind::Bool => matches!(argument, Argument::Bool(_)),
+            ArgumentKind::List => matches!(argument, Argument::List(_)),
+            ArgumentKind::Map => matches!(argument, Argument::Map(_)),
+            ArgumentKind::Name | ArgumentKind::Label | ArgumentKind::Command => false,
+        };
+
+        if !matches {
+            let found = get_value_type(&argument.clone().into_value());
+            return Err(self.error(
+                ErrorKind::Type,
+                format!("Expected `{name}` to be a {kind}, not a {found}"),
+                Vec::new(),
+            ));
+        }
+
+        return Ok(argument)
+    }
+
+    // Checks the arguments against the command's parameters, before anything is evaluated
+    #[inline(never)]
+    fn check_arguments(&self, info: &CommandInfo, arguments: &[String]) -> Result<(), ScriptError> {
+        return info
+            .check_arguments(arguments, &self.labels)
+            .map_err(|error| return self.error(error.kind, error.message, error.notes))
+    }
+
+    fn call_host_command(
+        &mut self,
+        host_command: &HostCommand,
+        arguments: Vec<String>,
+    ) -> Result<(), ScriptError> {
+        self.check_arguments(&host_command.info, &arguments)?;
+
+        let parameters = &host_command.info.parameters;
+        let mut checked_arguments = Vec::new();
+        for (index, argument) in arguments.into_iter().enumerate() {
+            // Every argument past the last parameter belongs to it, as it is variadic
+            let parameter = &parameters[index.min(parameters.len() - 1)];
+
+            if matches!(
+                parameter.kind,
+                ArgumentKind::Name | ArgumentKind::Label | ArgumentKind::Command
+            ) {
+                checked_arguments.push(Argument::Name(argument));
+                continue;
             }
+
+            let value = self.evaluate(argument)?;
+            checked_arguments.push(
+                self.to_argument(value, &parameter.name, parameter.kind)
+                    .map_err(|mut error| {
+                        error
+                            .notes
+                            .push(format!("[Usage] {}", host_command.info.usage()));
+                        return error
+                    })?,
+            );
         }
+
+        let mut context = Context {
+            interpreter: self,
+            command: host_command.info.name.clone(),
+        };
+        return (host_command.function)(&mut context, &checked_arguments)
     }
 
-    pub fn interpret_label(&mut self, label_name: String) {
+    fn get_string_content(&self, string: String) -> Result<String, ScriptError> {
+        if string.starts_with('\"') && string.ends_with('\"') {
+            return self.interpolate(&ast::unescape(rem_first_and_last(&string)));
+        } else {
+            return Ok(string)
+        }
+    }
+
+    // Replaces every `{name}` with the value of variable `name`, `{{` and `}}` are escapes
+    fn interpolate(&self, string: &str) -> Result<String, ScriptError> {
+        let mut result = String::new();
+        let mut chars = string.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            if c == '{' && chars.peek() == Some(&'{') {
+                chars.next();
+                result.push('{');
+            } else if c == '}' && chars.peek() == Some(&'}') {
+                chars.next();
+                result.push('}');
+            } else if c == '{' {
+                let mut variable_name = String::new();
+                let mut is_closed = false;
+
+                for c in chars.by_ref() {
+                    if c == '}' {
+                        is_closed = true;
+                        break;
+                    }
+                    variable_name.push(c);
+                }
+
+                if !is_closed {
+                    return Err(self.error(
+                        ErrorKind::Syntax,
+                        format!("Placeholder `{{{variable_name}` was never closed."),
+                        vec![
+                            "[Help] Add the missing `}` or use `{{` to write a literal `{`."
+                                .to_string(),
+                        ],
+                    ));
+                }
+
+                let variable_name = variable_name.trim().to_string();
+
+                if get_type(variable_name.clone()) != ast::Types::Identifier {
+                    return Err(self.error(
+                        ErrorKind::Syntax,
+                        format!("Placeholder `{{{variable_name}}}` is not a valid variable name."),
+                        vec!["[Help] Use `{{` and `}}` to write literal braces.".to_string()],
+                    ));
+                }
+
+                if !self.variables.contains_key(&variable_name) {
+                    return Err(self.error(
+                        ErrorKind::Variable,
+                        format!("Variable `{variable_name}` used in string interpolation does not exist."),
+                        vec![
+                            format!("[Help] Define it with `var {variable_name} <value>` before using `{{{variable_name}}}`."),
+                        ],
+                    ));
+                }
+
+                result.push_str(&self.get_variable(variable_name)?.0);
+            } else {
+                result.push(c);
+            }
+        }
+
+        return Ok(result)
+    }
+
+    pub fn interpret_label(&mut self, label_name: String) -> Result<(), ScriptError> {
+        if let Some(max_call_depth) = self.limits.max_call_depth {
+            if self.call_depth >= max_call_depth {
+                return Err(self.error(
+                    ErrorKind::CallDepthLimit,
+                    format!(
+                        "Call depth limit of {max_call_depth} reached in label `{}`",
+                        self.current_label
+                    ),
+                    vec![
+                        "[Note] Every jump which has not finished yet counts towards the depth"
+                            .to_string(),
+                        "[Help] Look for labels which keep jumping to each other, or raise the limit using `--max-depth=<depth>`".to_string(),
+                    ],
+                ));
+            }
+        }
+
+        let caller = std::mem::replace(&mut self.current_label, label_name.clone());
+        self.call_depth += 1;
+        let result = self.interpret_lines(label_name);
+        self.call_depth -= 1;
+        self.current_label = caller;
+
+        return result
+    }
+
+    fn interpret_lines(&mut self, label_name: String) -> Result<(), ScriptError> {
         for (line_number, line) in get_code_from(self.labels.clone(), label_name.clone()) {
             let command = line.0[0].clone();
             let command = command.as_str();
-            self.current_line_number = line_number + 1;
+            self.current_line_number = line_number;
             self.current_line_code = line.0.join(" ").clone();
 
             let arguments = line.0[1..].to_vec();
             let _no_of_args = arguments.len();
 
-            self.interpret_command(command, arguments, label_name.clone());
+            self.check_limits(&label_name)?;
+            self.interpret_command(command, arguments, label_name.clone())?;
+            self.check_memory(&label_name)?;
+        }
+
+        Ok(())
+    }
+
+    // Counts the instruction about to run and stops the script once it is over budget or out of time
+    fn check_limits(&mut self, label_name: &str) -> Result<(), ScriptError> {
+        self.instructions += 1;
+
+        if let Some(max_instructions) = self.limits.max_instructions {
+            if self.instructions > max_instructions {
+                return Err(self.error(
+                    ErrorKind::InstructionLimit,
+                    format!(
+                        "Instruction limit of {max_instructions} reached in label `{label_name}`"
+                    ),
+                    vec!["[Help] Raise the limit using `--max-instructions=<count>`".to_string()],
+                ));
+            }
+        }
+
+        if let (Some(timeout), Some(started_at)) = (self.limits.timeout, self.started_at) {
+            if started_at.elapsed() > timeout {
+                return Err(self.error(
+                    ErrorKind::TimeLimit,
+                    format!(
+                        "Time limit of {}s reached in label `{label_name}`",
+                        timeout.as_secs_f64()
+                    ),
+                    vec!["[Help] Raise the limit using `--timeout=<seconds>`".to_string()],
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    // Only adds up the variables when a memory limit is set, as it has to walk all of them
+    fn check_memory(&self, label_name: &str) -> Result<(), ScriptError> {
+        if let Some(max_memory) = self.limits.max_memory {
+            let used: usize = self
+                .variables
+                .iter()
+                .map(|(name, value)| name.len() + value.size())
+                .sum();
+
+            if used > max_memory {
+                return Err(self.error(
+                    ErrorKind::MemoryLimit,
+                    format!("Memory limit of {max_memory} bytes reached in label `{label_name}`"),
+                    vec![
+                        format!("[Note] The variables hold about {used} bytes"),
+                        "[Help] Raise the limit using `--max-memory=<bytes>`".to_string(),
+                    ],
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn interpret_command(
+        &mut self,
+        command: &str,
+        arguments: Vec<String>,
+        label_name: String,
+    ) -> Result<(), ScriptError> {
+        if let Some(host_command) = self.host_commands.get(command).cloned() {
+            return self.call_host_command(&host_command, arguments)
+        }
+
+        // Unknown commands are reported by `interpret_builtin`
+        if let Some(info) = commands::find(command) {
+            self.check_arguments(info, &arguments)?;
         }
+
+        match command {
+            "jmp" => {
+                self.interpret_label(arguments[0].clone())?;
+            }
+
+            "cmd_eq" => {
+                if self.equals(arguments[0].clone(), arguments[1].clone())? {
+                    self.interpret_command(&arguments[2], arguments[3..].to_vec(), label_name)?
+                }
+            }
+
+            "foreach" => {
+                for item in self.get_list(arguments[1].clone())? {
+                    self.variables.insert(arguments[0].clone(), item);
+                    self.interpret_label(arguments[2].clone())?;
+                }
+            }
+
+            "try" => {
+                match self.interpret_label(arguments[0].clone()) {
+                    Ok(()) => {}
+
+                    // Running out of a limit ends the script, even inside `try`
+                    Err(error) if error.kind.is_limit() => return Err(error),
+
+                    Err(error) => {
+                        self.variables.insert(
+                            String::from("ERR_MSG"),
+                            Value::Literal(to_string_literal(&error.message)),
+                        );
+                        self.variables.insert(
+                            String::from("ERR_LINE"),
+                            Value::Literal(error.line_number.to_string()),
+                        );
+                        self.variables.insert(
+                            String::from("ERR_KIND"),
+                            Value::Literal(to_string_literal(&error.kind.to_string())),
+                        );
+
+                        self.interpret_label(arguments[1].clone())?;
+                    }
+                }
+            }
+
+            _ => return self.interpret_builtin(command, arguments, label_name),
+        }
+
+        Ok(())
     }
 
-    pub fn interpret_command(&mut self, command: &str, arguments: Vec<String>, label_name: String) {
+    // Every command which does not jump to other labels, kept out of `interpret_command` so that
+    // the stack frames of recursive jumps stay small
+    #[inline(never)]
+    fn interpret_builtin(
+        &mut self,
+        command: &str,
+        arguments: Vec<String>,
+        label_name: String,
+    ) -> Result<(), ScriptError> {
         let no_of_args = arguments.len();
         match command {
-            // Comment
             "cmt" => {
                 // Ignore comments
             }
 
             "var" => {
-                if no_of_args != 2 {
-                    println!("[Error] Expected exactly 2 arguments, found {no_of_args}");
-                    println!("[Code]");
-                    println!("{} | {}", self.current_line_number, self.current_line_code);
-                    println!("[Usage] var <variable_name> <variable_value>");
-                    process::exit(1);
-                }
-
                 let variable_name = arguments[0].clone();
                 let variable_value = arguments[1].clone();
 
-                self.variables.insert(variable_name, variable_value);
-            }
+                // Lists and maps are shared, `var alias list` refers to the variable holding them
+                let target = self.resolve_variable_name(variable_value.clone());
+                let is_collection = matches!(
+                    self.variables.get(&target),
+                    Some(Value::List(_) | Value::Map(_))
+                );
 
-            "jmp" => {
-                if no_of_args != 1 {
-                    println!("[Error] Expected exactly 1 argument, found {no_of_args}");
-                    println!("[Code]");
-                    println!("{} | {}", self.current_line_number, self.current_line_code);
-                    println!("[Usage] jmp <label_name>");
-                    process::exit(1);
-                }
-
-                if has_label(self.labels.clone(), arguments[0].clone()) {
-                    self.interpret_label(arguments[0].clone());
+                if get_type(variable_value.clone()) == ast::Types::Identifier && is_collection {
+                    if target != variable_name {
+                        self.variables.insert(variable_name, Value::Literal(target));
+                    }
                 } else {
-                    println!("[Error] Label `{}` does not exist.", arguments[0].clone());
-                    println!("[Code]");
-                    println!("{} | {}", self.current_line_number, self.current_line_code);
-                    println!("[Note] The label needs to exist");
-                    process::exit(1);
+                    // Interpolated once here, so `var s "{s}b"` appends instead of referring to itself
+                    let value = self.evaluate(variable_value)?;
+                    self.variables.insert(variable_name, value);
                 }
             }
 
             "require" => {
-                if no_of_args != 1 {
-                    println!("[Error] Expected exactly 1 argument, found {no_of_args}");
-                    println!("[Code]");
-                    println!("{} | {}", self.current_line_number, self.current_line_code);
-                    println!("[Usage] require <variable_name>");
-                    process::exit(1);
-                }
-
                 if self.variables.clone().get(&arguments[0]).is_none() {
-                    println!(
-                        "[Error] Variable `{}` does not exist, but is required in label `{}`",
-                        arguments[0], label_name
-                    );
-                    println!("[Code]");
-                    println!("{} | {}", self.current_line_number, self.current_line_code);
-                    process::exit(1);
+                    return Err(self.error(
+                        ErrorKind::Variable,
+                        format!(
+                            "Variable `{}` does not exist, but is required in label `{}`",
+                            arguments[0], label_name
+                        ),
+                        Vec::new(),
+                    ));
                 }
             }
 
-            // Test if >=
-            "test_gt_eq" => {
-                if no_of_args != 2 {
-                    println!("[Error] Expected exactly 2 arguments, found {no_of_args}");
-                    println!("[Code]");
-                    println!("{} | {}", self.current_line_number, self.current_line_code);
-                    println!("[Usage] test_gt_eq <number1> <number2>");
-                    process::exit(1);
-                }
-
-                let n1 = {
-                    if get_type(arguments[0].clone()) == ast::Types::Identifier {
-                        self.get_variable(arguments[0].clone()).0
-                    } else {
-                        arguments[0].clone()
-                    }
-                };
-
-                let n2 = {
-                    if get_type(arguments[0].clone()) == ast::Types::Identifier {
-                        self.get_variable(arguments[0].clone()).0
-                    } else {
-                        arguments[1].clone()
-                    }
-                };
+            "test_gt_eq" | "test_lt_eq" | "test_gt" | "test_lt" => {
+                let n1 = self.resolve(arguments[0].clone())?;
+                let n2 = self.resolve(arguments[1].clone())?;
 
                 if get_type(n1.clone()) != ast::Types::Number {
-                    println!(
-                        "[Error] Expected the first value to be a Number, not a {}",
-                        get_type(n1)
-                    );
-                    println!("[Code]");
-                    println!("{} | {}", self.current_line_number, self.current_line_code);
-                    println!("[Usage] test_gt_eq <number1> <number2>");
-                    process::exit(1);
+                    return Err(self.error(
+                        ErrorKind::Type,
+                        format!(
+                            "Expected the first value to be a Number, not a {}",
+                            get_type(n1)
+                        ),
+                        vec![usage_note(command)],
+                    ));
                 }
 
                 if get_type(n2.clone()) != ast::Types::Number {
-                    println!(
-                        "[Error] Expected the first value to be a Number, not a {}",
-                        get_type(n2)
-                    );
-                    println!("[Code]");
-                    println!("{} | {}", self.current_line_number, self.current_line_code);
-                    println!("[Usage] test_gt_eq <number1> <number2>");
-                    process::exit(1);
+                    return Err(self.error(
+                        ErrorKind::Type,
+                        format!(
+                            "Expected the second value to be a Number, not a {}",
+                            get_type(n2)
+                        ),
+                        vec![usage_note(command)],
+                    ));
                 }
 
-                self.variables.insert(String::from("TEMP"), {
-                    if n1.parse::<f32>().unwrap() >= n2.parse::<f32>().unwrap() {
-                        "1".to_string()
-                    } else {
-                        "0".to_string()
-                    }
-                });
+                let (n1, n2) = (parse_number(&n1), parse_number(&n2));
+                let result = match command {
+                    "test_gt_eq" => n1 >= n2,
+                    "test_lt_eq" => n1 <= n2,
+                    "test_gt" => n1 > n2,
+                    _ => n1 < n2,
+                };
+
+                self.variables.insert(
+                    destination(&arguments, 2),
+                    Value::Literal(result.to_string()),
+                );
             }
 
             "test_eq" => {
-                if no_of_args != 2 {
-                    println!("[Error] Expected exactly 2 arguments, found {no_of_args}");
-                    println!("[Code]");
-                    println!("{} | {}", self.current_line_number, self.current_line_code);
-                    println!("[Usage] test_eq <value1> <value2>");
-                    process::exit(1);
+                let result = self.equals(arguments[0].clone(), arguments[1].clone())?;
+
+                self.variables.insert(
+                    destination(&arguments, 2),
+                    Value::Literal(result.to_string()),
+                );
+            }
+
+            "and" | "or" | "xor" => {
+                let b1 = self.get_bool(arguments[1].clone())?;
+                let b2 = self.get_bool(arguments[2].clone())?;
+
+                let result = match command {
+                    "and" => b1 && b2,
+                    "or" => b1 || b2,
+                    _ => b1 != b2,
+                };
+
+                self.variables
+                    .insert(arguments[0].clone(), Value::Literal(result.to_string()));
+            }
+
+            "not" => {
+                let result = !self.get_bool(arguments[1].clone())?;
+
+                self.variables
+                    .insert(arguments[0].clone(), Value::Literal(result.to_string()));
+            }
+
+            "add" | "sub" | "mul" | "div" | "mod" => {
+                let n1 = self.get_number(arguments[1].clone())?;
+                let n2 = self.get_number(arguments[2].clone())?;
+
+                if matches!(command, "div" | "mod") && n2.as_float() == 0.0 {
+                    return Err(self.error(
+                        ErrorKind::Arithmetic,
+                        "Division by zero.".to_string(),
+                        Vec::new(),
+                    ));
                 }
 
-                let n1 = {
-                    if get_type(arguments[0].clone()) == ast::Types::Identifier {
-                        self.get_variable(arguments[0].clone()).0
-                    } else {
-                        arguments[0].clone()
+                // Integers stay Integers (and must not overflow), anything else becomes a Float
+                let result = match (n1, n2) {
+                    (Number::Integer(i1), Number::Integer(i2)) => match command {
+                        "add" => i1.checked_add(i2),
+                        "sub" => i1.checked_sub(i2),
+                        "mul" => i1.checked_mul(i2),
+                        "div" => i1.checked_div(i2),
+                        _ => i1.checked_rem(i2),
                     }
-                };
+                    .map(Number::Integer),
 
-                let n2 = {
-                    if get_type(arguments[0].clone()) == ast::Types::Identifier {
-                        self.get_variable(arguments[0].clone()).0
-                    } else {
-                        arguments[1].clone()
+                    _ => {
+                        let (f1, f2) = (n1.as_float(), n2.as_float());
+                        Some(Number::Float(match command {
+                            "add" => f1 + f2,
+                            "sub" => f1 - f2,
+                            "mul" => f1 * f2,
+                            "div" => f1 / f2,
+                            _ => f1 % f2,
+                        }))
+                        .filter(|number| number.as_float().is_finite())
                     }
                 };
 
-                self.variables.insert(String::from("TEMP"), {
-                    if n1 == n2 {
-                        "1".to_string()
-                    } else {
-                        "0".to_string()
+                match result {
+                    Some(result) => {
+                        self.variables
+                            .insert(arguments[0].clone(), Value::Literal(result.to_string()));
                     }
-                });
-            }
 
-            "test_lt_eq" => {
-                if no_of_args != 2 {
-                    println!("[Error] Expected exactly 2 arguments, found {no_of_args}");
-                    println!("[Code]");
-                    println!("{} | {}", self.current_line_number, self.current_line_code);
-                    println!("[Usage] test_gt_eq <number1> <number2>");
-                    process::exit(1);
+                    None => {
+                        return Err(self.error(
+                            ErrorKind::Arithmetic,
+                            format!("The result of `{command} {n1} {n2}` is too large to be stored."),
+                            vec![
+                                format!("[Note] Integers must be between {} and {}, use `to_float` for larger numbers", i64::MIN, i64::MAX),
+                            ],
+                        ));
+                    }
                 }
+            }
 
-                let n1 = {
-                    if get_type(arguments[0].clone()) == ast::Types::Identifier {
-                        self.get_variable(arguments[0].clone()).0
-                    } else {
-                        arguments[0].clone()
+            "to_int" => {
+                let integer = match self.get_number(arguments[1].clone())? {
+                    Number::Integer(integer) => integer,
+
+                    // Rounds towards zero
+                    #[allow(clippy::cast_possible_truncation)]
+                    Number::Float(float) if float.abs() < 9.2e18 => float.trunc() as i64,
+
+                    Number::Float(float) => {
+                        return Err(self.error(
+                            ErrorKind::Conversion,
+                            format!("`{float:e}` is too large to be an Integer."),
+                            vec![format!(
+                                "[Note] Integers must be between {} and {}",
+                                i64::MIN,
+                                i64::MAX
+                            )],
+                        ));
                     }
                 };
 
-                let n2 = {
-                    if get_type(arguments[0].clone()) == ast::Types::Identifier {
-                        self.get_variable(arguments[0].clone()).0
-                    } else {
-                        arguments[1].clone()
-                    }
+                self.variables
+                    .insert(arguments[0].clone(), Value::Literal(integer.to_string()));
+            }
+
+            "to_float" => {
+                let float = Number::Float(self.get_number(arguments[1].clone())?.as_float());
+
+                self.variables
+                    .insert(arguments[0].clone(), Value::Literal(float.to_string()));
+            }
+
+            "typeof" => {
+                let value_type = self.value_type(arguments[1].clone())?;
+
+                self.variables.insert(
+                    arguments[0].clone(),
+                    Value::Literal(to_string_literal(&value_type.to_string())),
+                );
+            }
+
+            "to_str" => {
+                let content = self.get_content(arguments[1].clone())?;
+
+                // Numbers are written the canonical way, so `0xFF` becomes "255". Strings stay as
+                // they are, even if they look like numbers
+                let is_number = self.value_type(arguments[1].clone())? == ast::Types::Number;
+                let content = match parse_number(&content) {
+                    Some(number) if is_number => number.to_string(),
+                    _ => content,
                 };
 
-                if get_type(n1.clone()) != ast::Types::Number {
-                    println!(
-                        "[Error] Expected the first value to be a Number, not a {}",
-                        get_type(n1)
-                    );
-                    println!("[Code]");
-                    println!("{} | {}", self.current_line_number, self.current_line_code);
-                    println!("[Usage] test_lt_eq <number1> <number2>");
-                    process::exit(1);
-                }
+                self.variables.insert(
+                    arguments[0].clone(),
+                    Value::Literal(to_string_literal(&content)),
+                );
+            }
 
-                if get_type(n2.clone()) != ast::Types::Number {
-                    println!(
-                        "[Error] Expected the first value to be a Number, not a {}",
-                        get_type(n2)
-                    );
-                    println!("[Code]");
-                    println!("{} | {}", self.current_line_number, self.current_line_code);
-                    println!("[Usage] test_lt_eq <number1> <number2>");
-                    process::exit(1);
-                }
+            "to_num" => {
+                let content = self.get_content(arguments[1].clone())?;
+
+                match parse_number(content.trim()) {
+                    Some(number) => {
+                        self.variables
+                            .insert(arguments[0].clone(), Value::Literal(number.to_string()));
+                    }
 
-                self.variables.insert(String::from("TEMP"), {
-                    if n1.parse::<f32>().unwrap() <= n2.parse::<f32>().unwrap() {
-                        "1".to_string()
-                    } else {
-                        "0".to_string()
+                    None => {
+                        return Err(self.error(
+                            ErrorKind::Conversion,
+                            format!("`{content}` cannot be converted to a Number."),
+                            vec!["[Help] Check the value using `is_num` first".to_string()],
+                        ));
                     }
-                });
+                }
+            }
+
+            "is_num" | "is_str" => {
+                let value_type = self.value_type(arguments[0].clone())?;
+
+                // `is_num` also accepts strings which `to_num` can convert
+                let result = if command == "is_num" {
+                    value_type == ast::Types::Number
+                        || (value_type == ast::Types::String
+                            && parse_number(self.get_content(arguments[0].clone())?.trim())
+                                .is_some())
+                } else {
+                    value_type == ast::Types::String
+                };
+
+                self.variables.insert(
+                    destination(&arguments, 1),
+                    Value::Literal(result.to_string()),
+                );
+            }
+
+            "list_new" => {
+                let items = arguments[1..]
+                    .iter()
+                    .map(|argument| self.evaluate(argument.clone()))
+                    .collect::<Result<Vec<Value>, ScriptError>>()?;
+
+                self.variables
+                    .insert(arguments[0].clone(), Value::List(items));
+            }
+
+            "push" => {
+                let value = self.evaluate(arguments[1].clone())?;
+                self.get_list_mut(arguments[0].clone())?.push(value);
             }
 
-            "test_gt" => {
-                if no_of_args != 2 {
-                    println!("[Error] Expected exactly 2 arguments, found {no_of_args}");
-                    println!("[Code]");
-                    println!("{} | {}", self.current_line_number, self.current_line_code);
-                    println!("[Usage] test_gt <number1> <number2>");
-                    process::exit(1);
+            "pop" => match self.get_list_mut(arguments[1].clone())?.pop() {
+                Some(value) => {
+                    self.variables.insert(arguments[0].clone(), value);
                 }
 
-                let n1 = {
-                    if get_type(arguments[0].clone()) == ast::Types::Identifier {
-                        self.get_variable(arguments[0].clone()).0
-                    } else {
-                        arguments[0].clone()
+                None => {
+                    return Err(self.error(
+                        ErrorKind::Index,
+                        format!("Cannot pop from the empty List `{}`", arguments[1]),
+                        vec!["[Help] Check the length of the list using `len` first".to_string()],
+                    ));
+                }
+            },
+
+            "get" => {
+                let items = self.get_list(arguments[1].clone())?;
+                let index = self.get_index(arguments[2].clone(), items.len().saturating_sub(1))?;
+
+                match items.get(index) {
+                    Some(value) => {
+                        self.variables.insert(arguments[0].clone(), value.clone());
                     }
-                };
 
-                let n2 = {
-                    if get_type(arguments[0].clone()) == ast::Types::Identifier {
-                        self.get_variable(arguments[0].clone()).0
-                    } else {
-                        arguments[1].clone()
+                    None => {
+                        return Err(self.error(
+                            ErrorKind::Index,
+                            format!("Cannot get an item from the empty List `{}`", arguments[1]),
+                            Vec::new(),
+                        ));
                     }
-                };
+                }
+            }
 
-                if get_type(n1.clone()) != ast::Types::Number {
-                    println!(
-                        "[Error] Expected the first value to be a Number, not a {}",
-                        get_type(n1)
-                    );
-                    println!("[Code]");
-                    println!("{} | {}", self.current_line_number, self.current_line_code);
-                    println!("[Usage] test_gt <number1> <number2>");
-                    process::exit(1);
+            "set" => {
+                let items = self.get_list(arguments[0].clone())?;
+                if items.is_empty() {
+                    return Err(self.error(
+                        ErrorKind::Index,
+                        format!("Cannot set an item of the empty List `{}`", arguments[0]),
+                        vec!["[Help] Add items to the list using `push` first".to_string()],
+                    ));
                 }
 
-                if get_type(n2.clone()) != ast::Types::Number {
-                    println!(
-                        "[Error] Expected the first value to be a Number, not a {}",
-                        get_type(n2)
-                    );
-                    println!("[Code]");
-                    println!("{} | {}", self.current_line_number, self.current_line_code);
-                    println!("[Usage] test_gt <number1> <number2>");
-                    process::exit(1);
+                let index = self.get_index(arguments[1].clone(), items.len() - 1)?;
+                let value = self.evaluate(arguments[2].clone())?;
+                self.get_list_mut(arguments[0].clone())?[index] = value;
+            }
+
+            "len" => {
+                let length = self.get_list(arguments[1].clone())?.len();
+                self.variables
+                    .insert(arguments[0].clone(), Value::Literal(length.to_string()));
+            }
+
+            "insert" => {
+                let length = self.get_list(arguments[0].clone())?.len();
+                let index = self.get_index(arguments[1].clone(), length)?;
+                let value = self.evaluate(arguments[2].clone())?;
+                self.get_list_mut(arguments[0].clone())?
+                    .insert(index, value);
+            }
+
+            "remove" => {
+                let items = self.get_list(arguments[0].clone())?;
+                if items.is_empty() {
+                    return Err(self.error(
+                        ErrorKind::Index,
+                        format!(
+                            "Cannot remove an item from the empty List `{}`",
+                            arguments[0]
+                        ),
+                        Vec::new(),
+                    ));
                 }
 
-                self.variables.insert(String::from("TEMP"), {
-                    if n1.parse::<f32>().unwrap() > n2.parse::<f32>().unwrap() {
-                        "1".to_string()
-                    } else {
-                        "0".to_string()
-                    }
-                });
+                let index = self.get_index(arguments[1].clone(), items.len() - 1)?;
+                self.get_list_mut(arguments[0].clone())?.remove(index);
             }
 
-            "test_lt" => {
-                if no_of_args != 2 {
-                    println!("[Error] Expected exactly 2 arguments, found {no_of_args}");
-                    println!("[Code]");
-                    println!("{} | {}", self.current_line_number, self.current_line_code);
-                    println!("[Usage] test_lt <number1> <number2>");
-                    process::exit(1);
+            "sort" => {
+                let items = self.get_list_mut(arguments[0].clone())?;
+
+                // Numbers are sorted by value, everything else by how it is printed
+                if items
+                    .iter()
+                    .all(|item| get_type(item.to_string()) == ast::Types::Number)
+                {
+                    items.sort_by(|a, b| {
+                        let a = parse_number(&a.to_string());
+                        let b = parse_number(&b.to_string());
+                        a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+                    });
+                } else {
+                    items.sort_by_key(ToString::to_string);
                 }
+            }
+
+            "join" => {
+                let separator = self.get_content(arguments[2].clone())?;
+
+                let joined = self
+                    .get_list(arguments[1].clone())?
+                    .iter()
+                    .map(|item| match item {
+                        Value::Literal(token) => self.get_string_content(token.clone()),
+                        Value::List(_) | Value::Map(_) => Ok(item.to_string()),
+                    })
+                    .collect::<Result<Vec<String>, ScriptError>>()?
+                    .join(&separator);
+
+                self.variables.insert(
+                    arguments[0].clone(),
+                    Value::Literal(to_string_literal(&joined)),
+                );
+            }
 
-                let n1 = {
-                    if get_type(arguments[0].clone()) == ast::Types::Identifier {
-                        self.get_variable(arguments[0].clone()).0
-                    } else {
-                        arguments[0].clone()
+            "map_new" => {
+                self.variables
+                    .insert(arguments[0].clone(), Value::Map(BTreeMap::new()));
+            }
+
+            "map_set" => {
+                let key = self.get_content(arguments[1].clone())?;
+                let value = self.evaluate(arguments[2].clone())?;
+                self.get_map_mut(arguments[0].clone())?.insert(key, value);
+            }
+
+            "map_get" => {
+                let key = self.get_content(arguments[2].clone())?;
+
+                match self.get_map(arguments[1].clone())?.get(&key) {
+                    Some(value) => {
+                        self.variables.insert(arguments[0].clone(), value.clone());
                     }
-                };
 
-                let n2 = {
-                    if get_type(arguments[0].clone()) == ast::Types::Identifier {
-                        self.get_variable(arguments[0].clone()).0
-                    } else {
-                        arguments[1].clone()
+                    None if no_of_args == 4 => {
+                        let default = self.evaluate(arguments[3].clone())?;
+                        self.variables.insert(arguments[0].clone(), default);
                     }
-                };
 
-                if get_type(n1.clone()) != ast::Types::Number {
-                    println!(
-                        "[Error] Expected the first value to be a Number, not a {}",
-                        get_type(n1)
-                    );
-                    println!("[Code]");
-                    println!("{} | {}", self.current_line_number, self.current_line_code);
-                    println!("[Usage] test_lt <number1> <number2>");
-                    process::exit(1);
+                    None => {
+                        return Err(self.error(
+                            ErrorKind::Key,
+                            format!("Key `{key}` does not exist in Map `{}`", arguments[1]),
+                            vec![
+                                "[Help] Pass a default value or check the key using `map_has` first".to_string(),
+                            ],
+                        ));
+                    }
                 }
+            }
 
-                if get_type(n2.clone()) != ast::Types::Number {
-                    println!(
-                        "[Error] Expected the first value to be a Number, not a {}",
-                        get_type(n2)
-                    );
-                    println!("[Code]");
-                    println!("{} | {}", self.current_line_number, self.current_line_code);
-                    println!("[Usage] test_lt <number1> <number2>");
-                    process::exit(1);
+            "map_has" => {
+                let key = self.get_content(arguments[1].clone())?;
+                let has_key = self.get_map(arguments[0].clone())?.contains_key(&key);
+
+                self.variables.insert(
+                    destination(&arguments, 2),
+                    Value::Literal(has_key.to_string()),
+                );
+            }
+
+            "map_del" => {
+                let key = self.get_content(arguments[1].clone())?;
+
+                if self
+                    .get_map_mut(arguments[0].clone())?
+                    .remove(&key)
+                    .is_none()
+                {
+                    return Err(self.error(
+                        ErrorKind::Key,
+                        format!("Key `{key}` does not exist in Map `{}`", arguments[0]),
+                        vec!["[Help] Check the key using `map_has` first".to_string()],
+                    ));
                 }
+            }
 
-                self.variables.insert(String::from("TEMP"), {
-                    if n1.parse::<f32>().unwrap() < n2.parse::<f32>().unwrap() {
-                        "1".to_string()
-                    } else {
-                        "0".to_string()
-                    }
-                });
+            "map_keys" => {
+                let keys = self
+                    .get_map(arguments[1].clone())?
+                    .keys()
+                    .map(|key| Value::Literal(to_string_literal(key)))
+                    .collect::<Vec<Value>>();
+
+                self.variables
+                    .insert(arguments[0].clone(), Value::List(keys));
             }
 
-            "cmd_eq" => {
-                if no_of_args < 4 {
-                    println!("[Error] Expected at least 4 arguments, found {no_of_args}");
-                    println!("[Code]");
-                    println!("{} | {}", self.current_line_number, self.current_line_code);
-                    println!("[Usage] cmd_eq <value1> <value2> <command> <args>...");
-                    process::exit(1);
+            "map_len" => {
+                let length = self.get_map(arguments[1].clone())?.len();
+                self.variables
+                    .insert(arguments[0].clone(), Value::Literal(length.to_string()));
+            }
+
+            "throw" => {
+                let message = self.get_content(arguments[0].clone())?;
+                return Err(self.error(ErrorKind::Thrown, message, Vec::new()));
+            }
+
+            "assert" => {
+                if !self.get_bool(arguments[0].clone())? {
+                    let message = match arguments.get(1) {
+                        Some(message) => self.get_content(message.clone())?,
+                        None => format!("Assertion `{}` failed.", arguments[0]),
+                    };
+
+                    return Err(self.error(ErrorKind::Assertion, message, Vec::new()));
                 }
+            }
 
-                let x1 = {
-                    if get_type(arguments[0].clone()) == ast::Types::Identifier {
-                        self.get_variable(arguments[0].clone()).0
-                    } else {
-                        arguments[0].clone()
-                    }
+            "assert_eq" | "assert_ne" => {
+                let is_equal = self.equals(arguments[0].clone(), arguments[1].clone())?;
+
+                if is_equal != (command == "assert_eq") {
+                    let v1 = self.get_content(arguments[0].clone())?;
+                    let v2 = self.get_content(arguments[1].clone())?;
+                    let message = match arguments.get(2) {
+                        Some(message) => self.get_content(message.clone())?,
+                        None if is_equal => "Expected the values to be different.".to_string(),
+                        None => "Expected the values to be equal.".to_string(),
+                    };
+
+                    return Err(self.error(
+                        ErrorKind::Assertion,
+                        message,
+                        vec![
+                            format!("[Left] {} = {v1}", arguments[0]),
+                            format!("[Right] {} = {v2}", arguments[1]),
+                        ],
+                    ));
+                }
+            }
+
+            "read_file" | "read_lines" => {
+                let path = self.get_path(arguments[1].clone())?;
+                self.check_permission(command, Capability::Read, Some(&path))?;
+                let content = self.read_file(&path)?;
+
+                let value = if command == "read_file" {
+                    Value::Literal(to_string_literal(&content))
+                } else {
+                    Value::List(
+                        content
+                            .lines()
+                            .map(|line| Value::Literal(to_string_literal(line)))
+                            .collect(),
+                    )
                 };
 
-                let x2 = {
-                    if get_type(arguments[1].clone()) == ast::Types::Identifier {
-                        self.get_variable(arguments[0].clone()).0
-                    } else {
-                        arguments[1].clone()
+                self.variables.insert(arguments[0].clone(), value);
+            }
+
+            "write_file" | "append_file" => {
+                let path = self.get_path(arguments[0].clone())?;
+                self.check_permission(command, Capability::Write, Some(&path))?;
+                let content = self.get_content(arguments[1].clone())?;
+
+                let result = fs::OpenOptions::new()
+                    .create(true)
+                    .write(true)
+                    .append(command == "append_file")
+                    .truncate(command == "write_file")
+                    .open(&path)
+                    .and_then(|mut file| file.write_all(content.as_bytes()));
+
+                if let Err(error) = result {
+                    return Err(self.error(
+                        ErrorKind::Io,
+                        format!("Could not write to file `{}`", self.display_path(&path)),
+                        vec![format!("[Reason] {error}")],
+                    ));
+                }
+            }
+
+            "file_exists" => {
+                let path = self.get_path(arguments[0].clone())?;
+                self.check_permission(command, Capability::Read, Some(&path))?;
+                let result = path.is_file();
+
+                self.variables.insert(
+                    destination(&arguments, 1),
+                    Value::Literal(result.to_string()),
+                );
+            }
+
+            "env_get" => {
+                self.check_permission(command, Capability::Env, None)?;
+                let name = self.get_content(arguments[1].clone())?;
+
+                match env::var_os(&name) {
+                    Some(value) => {
+                        let value = to_string_literal(&value.to_string_lossy());
+                        self.variables
+                            .insert(arguments[0].clone(), Value::Literal(value));
+                    }
+
+                    None if no_of_args == 3 => {
+                        let default = self.evaluate(arguments[2].clone())?;
+                        self.variables.insert(arguments[0].clone(), default);
                     }
-                };
 
-                if x1 == x2 {
-                    self.interpret_command(&arguments[2], arguments[3..].to_vec(), label_name)
+                    None => {
+                        return Err(self.error(
+                            ErrorKind::Variable,
+                            format!("Environment variable `{name}` is not set"),
+                            vec![format!(
+                                "[Help] Pass a default value, e.g. `env_get {} \"{name}\" \"\"`",
+                                arguments[0]
+                            )],
+                        ));
+                    }
                 }
             }
 
-            "print" => {
-                if no_of_args != 1 {
-                    println!("[Error] Expected exactly 1 argument, found {no_of_args}");
-                    println!("[Code]");
-                    println!("{} | {}", self.current_line_number, self.current_line_code);
-                    println!("[Usage] print <value>");
-                    process::exit(1);
+            "env_set" => {
+                self.check_permission(command, Capability::Env, None)?;
+                let name = self.get_content(arguments[0].clone())?;
+                let value = self.get_content(arguments[1].clone())?;
+
+                // `set_var` panics on these
+                if name.is_empty() || name.contains(['=', '\0']) || value.contains('\0') {
+                    return Err(self.error(
+                        ErrorKind::Arguments,
+                        format!("`{name}` cannot be set as an environment variable"),
+                        vec!["[Note] The name must not be empty or contain `=`".to_string()],
+                    ));
                 }
 
-                let x1 = {
-                    if get_type(arguments[0].clone()) == ast::Types::Identifier {
-                        self.get_variable(arguments[0].clone()).0
-                    } else {
-                        arguments[0].clone()
+                env::set_var(name, value);
+            }
+
+            "exec" | "exec_inherit" => {
+                self.check_permission(command, Capability::Run, None)?;
+
+                let (destination, arguments) = match command {
+                    "exec" => (Some(arguments[0].clone()), &arguments[1..]),
+                    _ => (None, &arguments[..]),
+                };
+                let program = self.get_content(arguments[0].clone())?;
+                let args = arguments[1..]
+                    .iter()
+                    .map(|argument| return self.get_content(argument.clone()))
+                    .collect::<Result<Vec<String>, ScriptError>>()?;
+
+                // Programs given as a path are found like files, relative to the script
+                let program_path = if program.contains(['/', '\\']) {
+                    self.script_dir.join(&program)
+                } else {
+                    PathBuf::from(&program)
+                };
+
+                // Arguments are passed on as they are, without a shell in between
+                let mut process = process::Command::new(program_path);
+                process.args(&args);
+
+                let result = if destination.is_some() {
+                    process.stdin(process::Stdio::null()).output()
+                } else {
+                    // Keeps what the script printed so far in front of the program's output
+                    self.output.flush().ok();
+                    process.status().map(|status| process::Output {
+                        status,
+                        stdout: Vec::new(),
+                        stderr: Vec::new(),
+                    })
+                };
+
+                let output = match result {
+                    Ok(output) => output,
+
+                    Err(error) => {
+                        return Err(self.error(
+                            ErrorKind::Io,
+                            format!("Could not run `{program}`"),
+                            vec![format!("[Reason] {error}")],
+                        ));
                     }
                 };
 
-                println!("{}", get_string_content(x1));
+                // Like shells do, trailing new lines are removed
+                let text = |bytes: &[u8]| {
+                    return to_string_literal(
+                        String::from_utf8_lossy(bytes).trim_end_matches(['\n', '\r']),
+                    );
+                };
+
+                if let Some(destination) = destination {
+                    self.variables
+                        .insert(destination, Value::Literal(text(&output.stdout)));
+                    self.variables.insert(
+                        String::from("EXEC_STDERR"),
+                        Value::Literal(text(&output.stderr)),
+                    );
+                }
+
+                // Programs stopped by a signal have no exit code
+                let status = output.status.code().unwrap_or(-1);
+                self.variables.insert(
+                    String::from("EXEC_STATUS"),
+                    Value::Literal(status.to_string()),
+                );
+            }
+
+            "print" => {
+                let content = self.get_content(arguments[0].clone())?;
+                self.print(&content)?;
             }
 
             _ => {
-                println!("[Error] Unknown command `{command}`");
-                println!("[Code]");
-                println!("{} | {}", self.current_line_number, self.current_line_code);
-                process::exit(1);
+                return Err(self.error(
+                    ErrorKind::Syntax,
+                    format!("Unknown command `{command}`"),
+                    Vec::new(),
+                ));
             }
         }
+
+        Ok(())
+    }
+
+    pub fn interpret(&mut self) -> Result<(), ScriptError> {
+        if !has_label(self.labels.clone(), ".ENTRY".to_string()) {
+            return Err(self.error(
+                ErrorKind::Label,
+                "Label `.ENTRY` does not exist. (.ENTRY is the main entry point of the script.)"
+                    .to_string(),
+                vec!["[Help] Add a label named `.ENTRY` using `label .ENTRY`".to_string()],
+            ));
+        }
+
+        return self.interpret_from(".ENTRY".to_string())
     }
 
-    pub fn interpret(&mut self) {
-        self.variables.insert(String::from("TEMP"), String::new());
-        self.interpret_label(".ENTRY".to_string());
+    // Runs the script starting at `label_name` instead of `.ENTRY`, used by `--test`
+    pub fn interpret_from(&mut self, label_name: String) -> Result<(), ScriptError> {
+        self.variables
+            .insert(String::from("TEMP"), Value::Literal(String::new()));
+        self.variables.insert(
+            String::from("ARGC"),
+            Value::Literal(self.args.len().to_string()),
+        );
+        self.variables.insert(
+            String::from("ARGV"),
+            Value::List(
+                self.args
+                    .iter()
+                    .map(|arg| Value::Literal(to_string_literal(arg)))
+                    .collect(),
+            ),
+        );
+        self.instructions = 0;
+        self.started_at = Some(Instant::now());
+        return self.interpret_label(label_name)
     }
 }
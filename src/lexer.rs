@@ -1,70 +1,202 @@
-use std::process;
+use crate::error::{ErrorKind, ScriptError};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Line(pub Vec<String>);
 
+const TRIPLE_QUOTE: [char; 3] = ['"', '"', '"'];
+
+// What a piece of source code is, see `tokens`
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum TokenKind {
+    // Spaces and tabs
+    Space,
+    Newline,
+    // `# ...` up to the end of the line, or `#[ ... ]#`
+    Comment,
+    // `"..."` or `"""..."""` with its quotes. An unended string runs to the end of its line, or of
+    // the code for `"""`
+    String,
+    Word,
+}
+
+// A piece of source code as it is written, with the line and character it starts at (from 0)
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub text: String,
+    pub line: usize,
+    pub character: usize,
+}
+
+// Where `needle` first ends in `haystack`, searching from `from`
+fn end_of(haystack: &[char], from: usize, needle: &[char]) -> Option<usize> {
+    return haystack
+        .get(from..)?
+        .windows(needle.len())
+        .position(|window| window == needle)
+        .map(|position| from + position + needle.len())
+}
+
+// Splits code into tokens, keeping every character. Lexing, formatting, highlighting and the
+// language server all read code through this, so they agree on where strings and comments are
 #[must_use]
-pub fn lex(code: String) -> Vec<Line> {
+pub fn tokens(code: &str) -> Vec<Token> {
+    let characters = code.chars().collect::<Vec<char>>();
+    let is_space = |c: &char| return [' ', '\t', '\r'].contains(c);
+    let mut tokens = Vec::new();
+    let (mut index, mut line, mut character) = (0, 0, 0);
+
+    while index < characters.len() {
+        let rest = &characters[index..];
+        let line_end = rest.iter().position(|&c| c == '\n').unwrap_or(rest.len());
+
+        let (kind, length) = if rest[0] == '\n' {
+            (TokenKind::Newline, 1)
+        } else if is_space(&rest[0]) {
+            let length = rest.iter().position(|c| !is_space(c));
+            (TokenKind::Space, length.unwrap_or(rest.len()))
+        } else if rest.starts_with(&['#', '[']) {
+            let length = end_of(rest, 2, &[']', '#']);
+            (TokenKind::Comment, length.unwrap_or(rest.len()))
+        } else if rest[0] == '#' {
+            (TokenKind::Comment, line_end)
+        } else if rest.starts_with(&TRIPLE_QUOTE) {
+            let length = end_of(rest, 3, &TRIPLE_QUOTE);
+            (TokenKind::String, length.unwrap_or(rest.len()))
+        } else if rest[0] == '"' {
+            let length = end_of(&rest[..line_end], 1, &['"']);
+            (TokenKind::String, length.unwrap_or(line_end))
+        } else {
+            let length = rest
+                .iter()
+                .position(|c| return is_space(c) || ['\n', '#', '"'].contains(c));
+            (TokenKind::Word, length.unwrap_or(rest.len()))
+        };
+
+        tokens.push(Token {
+            kind,
+            text: rest[..length].iter().collect(),
+            line,
+            character,
+        });
+
+        for &c in &rest[..length] {
+            if c == '\n' {
+                line += 1;
+                character = 0;
+            } else {
+                character += 1;
+            }
+        }
+        index += length;
+    }
+
+    return tokens
+}
+
+// Every comment with the 1-based line it starts on
+pub(crate) fn comments(code: &str) -> Vec<(usize, String)> {
+    return tokens(code.replace('\r', "").trim_end())
+        .into_iter()
+        .filter(|token| token.kind == TokenKind::Comment)
+        .map(|token| return (token.line + 1, token.text.trim_end().to_string()))
+        .collect()
+}
+
+// Turns the text between `"""` into a string token. A line break right after the opening quotes is
+// dropped, and closing quotes on their own line remove their indentation from every line
+fn multi_line_token(content: &str) -> String {
+    let mut lines: Vec<&str> = content.split('\n').collect();
+
+    if lines.len() > 1 && lines[0].trim().is_empty() {
+        lines.remove(0);
+    }
+    if lines.len() > 1 && lines[lines.len() - 1].trim().is_empty() {
+        let indentation = lines.pop().unwrap_or_default();
+        lines = lines
+            .iter()
+            .map(|line| return line.strip_prefix(indentation).unwrap_or(line))
+            .collect();
+    }
+
+    return format!("\"{}\"", lines.join("\n"))
+}
+
+pub fn lex(code: String) -> Result<Vec<Line>, ScriptError> {
     if code.is_empty() {
-        println!("[Error] Empty code.");
-        println!("[Reason] Source code cannot be empty.");
-        process::exit(1);
+        return Err(ScriptError {
+            kind: ErrorKind::Syntax,
+            message: "Empty code.".to_string(),
+            line_number: 0,
+            line_code: String::new(),
+            notes: vec!["[Reason] Source code cannot be empty.".to_string()],
+        });
     }
 
-    let mut lexed_code: Vec<Line> = Vec::new();
-    let mut lexed_code_line: Vec<String> = Vec::new();
-    let mut temp: String = String::new();
-    let mut is_string: bool = false;
-    let mut temp_string: String = String::new();
-
-    for (line_number, line) in code
-        .replace(['\r', '\t'], "")
-        .trim()
-        .split('\n')
-        .enumerate()
-    {
-        let line_characters = &(*line).chars().collect::<Vec<char>>();
-        for (character_index, &c) in line_characters.clone().iter().enumerate() {
-            if c == '"' {
-                if !is_string {
-                    is_string = true
-                } else {
-                    is_string = false;
-                    temp_string.push(c);
+    let code = code.replace('\r', "");
+    // Only the end is trimmed, blank lines at the start still count towards line numbers
+    let code = code.trim_end();
+    let code_lines = code.split('\n').collect::<Vec<&str>>();
+    // Every line of code gets a line here, even if it is empty, so that line numbers stay right
+    let mut lexed_code = vec![Line(Vec::new()); code_lines.len()];
+    // A command goes on after a multi-line string, with the line the string ends on and the line
+    // of the command
+    let mut continued: Option<(usize, usize)> = None;
+
+    for token in tokens(code) {
+        let line = match continued {
+            Some((end_line, command_line)) if end_line == token.line => command_line,
+            _ => token.line,
+        };
+        let error = |message: &str, help: &str| {
+            return ScriptError {
+                kind: ErrorKind::Syntax,
+                message: message.to_string(),
+                line_number: token.line + 1,
+                line_code: code_lines[token.line].to_string(),
+                notes: vec![format!("[Help] {help}")],
+            }
+        };
+        let length = token.text.chars().count();
+
+        match token.kind {
+            // Comments are dropped here, so they cost nothing at runtime. This includes a
+            // `#!/usr/bin/env script-ll-2` line, which lets scripts be run directly
+            TokenKind::Comment if token.text.starts_with("#[") => {
+                if length < 4 || !token.text.ends_with("]#") {
+                    return Err(error(
+                        "Comment was never ended.",
+                        "Add the missing `]#` at the end of the comment.",
+                    ));
                 }
             }
+            TokenKind::Space | TokenKind::Newline | TokenKind::Comment => {}
 
-            if is_string && character_index == line_characters.len() - 1 {
-                println!("[Error] String was never ended.");
-                println!("[Code]\n{line} | {line_number}");
-                println!(r#"[Help] Add the missing `"` at the end of the string."#);
-                process::exit(1);
-            } else if is_string {
-                temp_string.push(c);
-            } else {
-                if !temp_string.is_empty() {
-                    lexed_code_line.push(temp_string.clone());
-                    temp_string = String::new();
+            TokenKind::String if token.text.starts_with(r#"""""#) => {
+                if length < 6 || !token.text.ends_with(r#"""""#) {
+                    return Err(error(
+                        "String was never ended.",
+                        r#"Add the missing `"""` at the end of the string."#,
+                    ));
                 }
 
-                if c == ' ' {
-                    lexed_code_line.push(temp.clone());
-                    temp = String::new();
-                } else if character_index == line_characters.len() - 1 && c != '"' {
-                    temp.push(c);
-                    lexed_code_line.push(temp.clone());
-                    temp = String::new();
-                } else if !vec!['\"'].contains(&c) {
-                    temp.push(c);
+                let content = &token.text[3..token.text.len() - 3];
+                lexed_code[line].0.push(multi_line_token(content));
+                continued = Some((token.line + content.matches('\n').count(), line));
+            }
+            TokenKind::String => {
+                if length < 2 || !token.text.ends_with('"') {
+                    return Err(error(
+                        "String was never ended.",
+                        r#"Add the missing `"` at the end of the string."#,
+                    ));
                 }
+                lexed_code[line].0.push(token.text);
             }
-        }
 
-        lexed_code_line.retain(|x| !(*x).is_empty());
-
-        lexed_code.push(Line(lexed_code_line));
-        lexed_code_line = Vec::new();
+            TokenKind::Word => lexed_code[line].0.push(token.text),
+        }
     }
 
-    return lexed_code
+    return Ok(lexed_code)
 }
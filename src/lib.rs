@@ -0,0 +1,53 @@
+#![warn(
+    clippy::all,
+    clippy::restriction,
+    clippy::pedantic,
+    clippy::nursery,
+    clippy::cargo
+)]
+
+use std::io::Write;
+
+use ast::Label;
+use error::ScriptError;
+use interpreter::Interpreter;
+
+pub mod ast;
+pub mod checker;
+pub mod commands;
+pub mod docs;
+pub mod error;
+pub mod formatter;
+pub mod highlight;
+pub mod host;
+pub mod interpreter;
+pub mod lexer;
+pub mod limits;
+pub mod linter;
+pub mod lsp;
+pub mod permissions;
+
+// Lexes a script and splits it into its labels
+pub fn parse(code: String) -> Result<Vec<Label>, ScriptError> {
+    return ast::get_labels(&lexer::lex(code)?)
+}
+
+// Runs a script the same way the command line does and returns the exit code.
+// `configure` can change the interpreter (output, permissions, ...) before the script starts,
+// errors are written to `stderr`.
+pub fn run(code: String, configure: impl FnOnce(&mut Interpreter), stderr: &mut dyn Write) -> i32 {
+    let result = parse(code).and_then(|labels| {
+        let mut interpreter = Interpreter::new(labels);
+        configure(&mut interpreter);
+        return interpreter.interpret()
+    });
+
+    match result {
+        Ok(()) => return 0,
+
+        Err(error) => {
+            writeln!(stderr, "{error}").ok();
+            return 1
+        }
+    }
+}
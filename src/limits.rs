@@ -0,0 +1,52 @@
+use std::time::Duration;
+
+// Every level of `jmp` uses native stack, so an unlimited depth would crash the interpreter. Even
+// in debug builds this many levels fit in the 2 MiB a spawned thread gets
+pub const DEFAULT_MAX_CALL_DEPTH: usize = 100;
+
+// Loops are jumps too, so the command line runs scripts on a thread with this much stack, which
+// fits `LARGE_STACK_MAX_CALL_DEPTH` levels in debug builds
+pub const LARGE_STACK_SIZE: usize = 256 * 1024 * 1024;
+pub const LARGE_STACK_MAX_CALL_DEPTH: usize = 10_000;
+
+// `None` means unlimited
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Limits {
+    pub max_instructions: Option<u64>,
+    pub timeout: Option<Duration>,
+    pub max_call_depth: Option<usize>,
+    // Roughly how many bytes all variables together may hold
+    pub max_memory: Option<usize>,
+}
+
+impl Limits {
+    #[must_use]
+    pub const fn none() -> Self {
+        return Self {
+            max_instructions: None,
+            timeout: None,
+            max_call_depth: None,
+            max_memory: None,
+        }
+    }
+}
+
+impl Limits {
+    // The defaults for a thread with `LARGE_STACK_SIZE` of stack
+    #[must_use]
+    pub const fn large_stack() -> Self {
+        return Self {
+            max_call_depth: Some(LARGE_STACK_MAX_CALL_DEPTH),
+            ..Self::none()
+        }
+    }
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        return Self {
+            max_call_depth: Some(DEFAULT_MAX_CALL_DEPTH),
+            ..Self::none()
+        }
+    }
+}
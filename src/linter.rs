@@ -0,0 +1,364 @@
+use std::collections::{BTreeMap, HashSet};
+
+use serde_json::{json, Value as Json};
+
+use crate::ast::{self, Label};
+use crate::commands::{self, ArgumentKind, CommandInfo, Parameter, Role};
+use crate::error::ScriptError;
+use crate::lexer::{self, Line};
+
+pub struct Rule {
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+pub const RULES: [Rule; 4] = [
+    Rule {
+        name: "stale-temp",
+        description: "TEMP is read after other commands ran since it was set",
+    },
+    Rule {
+        name: "unreachable-label",
+        description: "A label is never reached from .ENTRY or a test_* label",
+    },
+    Rule {
+        name: "unused-variable",
+        description: "A variable is assigned but never read, names starting with _ are ignored",
+    },
+    Rule {
+        name: "self-comparison",
+        description: "A value is compared with itself",
+    },
+];
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Lint {
+    pub rule: &'static str,
+    pub message: String,
+    pub line_number: usize,
+    pub line_code: String,
+    pub help: String,
+}
+
+impl std::fmt::Display for Lint {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "[Warning] {}", self.message)?;
+        write!(f, "\n[Code]\n{} | {}", self.line_number, self.line_code)?;
+        write!(
+            f,
+            "\n[Rule] {} (allow it with `# lint: allow({})`)",
+            self.rule, self.rule
+        )?;
+        write!(f, "\n[Help] {}", self.help)
+    }
+}
+
+#[must_use]
+pub fn find_rule(name: &str) -> Option<&'static Rule> {
+    return RULES.iter().find(|rule| rule.name == name)
+}
+
+// Finds likely bugs and bad style which are not errors. Rules named in `disabled` are skipped, and
+// a `# lint: allow(<rule>, <rule>)` comment skips them on its line
+pub fn lint(code: &str, disabled: &[String]) -> Result<Vec<Lint>, ScriptError> {
+    let lexed_code = lexer::lex(code.to_string())?;
+    let labels = ast::get_labels(&lexed_code)?;
+
+    let mut lints = Vec::new();
+    stale_temp(&labels, &mut lints);
+    unreachable_labels(&lexed_code, &labels, &mut lints);
+    unused_variables(&labels, &mut lints);
+    self_comparisons(&labels, &mut lints);
+
+    let allowed = allowed_rules(code);
+    lints.retain(|lint| {
+        let is_allowed = allowed
+            .get(&lint.line_number)
+            .is_some_and(|rules| rules.iter().any(|rule| rule == lint.rule));
+        return !is_allowed && !disabled.iter().any(|rule| rule == lint.rule)
+    });
+    lints.sort_by_key(|lint| lint.line_number);
+
+    return Ok(lints)
+}
+
+// The lints as a JSON array, for editors and CI
+#[must_use]
+pub fn to_json(lints: &[Lint]) -> String {
+    let lints = lints
+        .iter()
+        .map(|lint| {
+            return json!({
+                "rule": lint.rule,
+                "line": lint.line_number,
+                "code": lint.line_code,
+                "message": lint.message,
+                "help": lint.help,
+            })
+        })
+        .collect::<Vec<Json>>();
+
+    return format!("{:#}", Json::Array(lints))
+}
+
+// The rules allowed by `# lint: allow(...)` comments, by line number
+fn allowed_rules(code: &str) -> BTreeMap<usize, Vec<String>> {
+    let mut allowed: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+
+    for (line_number, comment) in lexer::comments(code) {
+        let Some((_, rest)) = comment.split_once("lint: allow(") else {
+            continue;
+        };
+        let rules = rest.split(')').next().unwrap_or_default();
+
+        allowed
+            .entry(line_number)
+            .or_default()
+            .extend(rules.split(',').map(|rule| return rule.trim().to_string()));
+    }
+
+    return allowed
+}
+
+// A command and the one it runs, e.g. `cmd_eq` and `print` for `cmd_eq x 1 print "one"`.
+// Unknown commands are left to the checker
+fn nested_commands(words: &[String]) -> Vec<(&'static CommandInfo, &[String])> {
+    let mut nested = Vec::new();
+    let mut words = words;
+
+    while let Some(info) = words.first().and_then(|command| commands::find(command)) {
+        nested.push((info, words));
+
+        match info
+            .parameters
+            .iter()
+            .position(|parameter| parameter.kind == ArgumentKind::Command)
+        {
+            Some(index) if words.len() > index + 1 => words = &words[index + 1..],
+            _ => break,
+        }
+    }
+
+    return nested
+}
+
+// The arguments of a command with their parameters, without the command it runs
+fn arguments<'a>(info: &'a CommandInfo, words: &'a [String]) -> Vec<(&'a Parameter, &'a String)> {
+    let mut arguments = Vec::new();
+
+    for (index, token) in words[1..].iter().enumerate() {
+        let parameter = info.parameters.get(index).or_else(|| {
+            info.parameters
+                .last()
+                .filter(|parameter| parameter.variadic)
+        });
+        match parameter {
+            Some(parameter) if parameter.kind != ArgumentKind::Command => {
+                arguments.push((parameter, token));
+            }
+            _ => break,
+        }
+    }
+
+    return arguments
+}
+
+fn is_assignment(parameter: &Parameter) -> bool {
+    return parameter.kind == ArgumentKind::Name && parameter.role == Role::Defines
+}
+
+fn assigned_variables(words: &[String]) -> Vec<String> {
+    return nested_commands(words)
+        .into_iter()
+        .flat_map(|(info, words)| {
+            return arguments(info, words)
+                .into_iter()
+                .filter(|(parameter, _)| is_assignment(parameter))
+                .map(|(_, token)| token.clone())
+                .collect::<Vec<String>>()
+        })
+        .collect()
+}
+
+// Variables used as arguments or in `{name}` placeholders
+fn read_variables(words: &[String]) -> Vec<String> {
+    let mut variables = Vec::new();
+
+    for (info, words) in nested_commands(words) {
+        for (parameter, token) in arguments(info, words) {
+            if is_assignment(parameter) || parameter.kind == ArgumentKind::Label {
+                continue;
+            }
+
+            if token.starts_with('"') {
+                variables.extend(placeholders(token));
+            } else if ast::is_identifier(token) {
+                variables.push(token.clone());
+            }
+        }
+    }
+
+    return variables
+}
+
+// The variable names in `{name}` placeholders, `{{` is an escaped `{`
+fn placeholders(string: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut rest = string;
+
+    while let Some(start) = rest.find('{') {
+        rest = &rest[start + 1..];
+        if let Some(escaped) = rest.strip_prefix('{') {
+            rest = escaped;
+            continue;
+        }
+        if let Some(end) = rest.find('}') {
+            names.push(rest[..end].to_string());
+            rest = &rest[end + 1..];
+        }
+    }
+
+    return names
+}
+
+// Whether a command may change TEMP, either by leaving out its destination or through a label
+fn sets_temp(words: &[String]) -> bool {
+    return nested_commands(words).into_iter().any(|(info, words)| {
+        let leaves_out_destination =
+            info.parameters
+                .iter()
+                .enumerate()
+                .any(|(index, parameter)| {
+                    return is_assignment(parameter)
+                        && parameter.optional
+                        && words.len() <= index + 1;
+                });
+        let runs_label = info
+            .parameters
+            .iter()
+            .any(|parameter| parameter.kind == ArgumentKind::Label);
+
+        return leaves_out_destination
+            || runs_label
+            || assigned_variables(words).contains(&"TEMP".to_string());
+    })
+}
+
+fn stale_temp(labels: &[Label], lints: &mut Vec<Lint>) {
+    for label in labels {
+        // Line which last set TEMP and how many commands ran since then
+        let mut set_at: Option<(usize, usize)> = None;
+
+        for (line_number, Line(words)) in &label.label_code {
+            let reads_temp = read_variables(words)
+                .iter()
+                .any(|variable| variable == "TEMP");
+
+            if let (true, Some((set_line, commands))) = (reads_temp, set_at) {
+                if commands > 0 {
+                    lints.push(Lint {
+                        rule: "stale-temp",
+                        message: format!(
+                            "`TEMP` is read {commands} command(s) after line {set_line} set it"
+                        ),
+                        line_number: *line_number,
+                        line_code: words.join(" "),
+                        help: "Store the result in its own variable, e.g. `test_eq a b is_equal`"
+                            .to_string(),
+                    });
+                }
+            }
+
+            if sets_temp(words) {
+                set_at = Some((*line_number, 0));
+            } else if let (false, Some((_, commands))) = (reads_temp, &mut set_at) {
+                *commands += 1;
+            }
+        }
+    }
+}
+
+fn unreachable_labels(lexed_code: &[Line], labels: &[Label], lints: &mut Vec<Lint>) {
+    let mut reached: HashSet<String> = labels
+        .iter()
+        .map(|label| label.label_name.clone())
+        .filter(|name| name == ".ENTRY" || name.starts_with("test_"))
+        .collect();
+    let mut pending: Vec<String> = reached.iter().cloned().collect();
+
+    while let Some(name) = pending.pop() {
+        for (_, Line(words)) in ast::get_code_from(labels.to_vec(), name) {
+            for (info, words) in nested_commands(&words) {
+                for (parameter, token) in arguments(info, words) {
+                    if parameter.kind == ArgumentKind::Label && reached.insert(token.clone()) {
+                        pending.push(token.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    for (index, Line(words)) in lexed_code.iter().enumerate() {
+        if let [command, name] = words.as_slice() {
+            if command == "label" && !reached.contains(name) {
+                lints.push(Lint {
+                    rule: "unreachable-label",
+                    message: format!("Label `{name}` is never reached"),
+                    line_number: index + 1,
+                    line_code: words.join(" "),
+                    help: "Remove it, or jump to it from a label which is reached".to_string(),
+                });
+            }
+        }
+    }
+}
+
+fn unused_variables(labels: &[Label], lints: &mut Vec<Lint>) {
+    let lines = labels.iter().flat_map(|label| &label.label_code);
+    let read = lines
+        .clone()
+        .flat_map(|(_, Line(words))| read_variables(words))
+        .collect::<HashSet<String>>();
+    let mut reported: HashSet<String> = HashSet::new();
+
+    for (line_number, Line(words)) in lines {
+        for variable in assigned_variables(words) {
+            if variable == "TEMP" || variable.starts_with('_') || read.contains(&variable) {
+                continue;
+            }
+            if reported.insert(variable.clone()) {
+                lints.push(Lint {
+                    rule: "unused-variable",
+                    message: format!("Variable `{variable}` is assigned but never read"),
+                    line_number: *line_number,
+                    line_code: words.join(" "),
+                    help: format!("Remove it, or name it `_{variable}` if it is unused on purpose"),
+                });
+            }
+        }
+    }
+}
+
+fn self_comparisons(labels: &[Label], lints: &mut Vec<Lint>) {
+    for (line_number, Line(words)) in labels.iter().flat_map(|label| &label.label_code) {
+        for (info, command_words) in nested_commands(words) {
+            let compared = arguments(info, command_words)
+                .into_iter()
+                .filter(|(parameter, _)| parameter.role == Role::Compared)
+                .map(|(_, token)| token)
+                .collect::<Vec<&String>>();
+
+            if let [first, second] = compared.as_slice() {
+                if first == second {
+                    lints.push(Lint {
+                        rule: "self-comparison",
+                        message: format!("`{}` compares `{first}` with itself", info.name),
+                        line_number: *line_number,
+                        line_code: words.join(" "),
+                        help: "The result never changes, compare with another value".to_string(),
+                    });
+                }
+            }
+        }
+    }
+}
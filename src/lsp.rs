@@ -0,0 +1,346 @@
+use std::collections::{BTreeMap, HashMap};
+use std::io::{self, BufRead, Write};
+
+use serde_json::{json, Value as Json};
+
+use crate::lexer::{self, Token, TokenKind};
+use crate::{checker, commands, docs};
+
+const SEVERITY_ERROR: u8 = 1;
+const COMPLETION_FUNCTION: u8 = 3;
+const COMPLETION_REFERENCE: u8 = 18;
+const SYMBOL_FUNCTION: u8 = 12;
+const METHOD_NOT_FOUND: i32 = -32601;
+
+// Reads one message and its `Content-Length` header, `None` once the input has ended
+fn read_message(input: &mut impl BufRead) -> io::Result<Option<Json>> {
+    let mut length = None;
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None)
+        }
+
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some(value) = header.strip_prefix("Content-Length:") {
+            length = value.trim().parse::<usize>().ok();
+        }
+    }
+
+    let Some(length) = length else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "Missing Content-Length header",
+        ));
+    };
+    let mut body = vec![0; length];
+    input.read_exact(&mut body)?;
+
+    return Ok(Some(serde_json::from_slice(&body)?))
+}
+
+fn write_message(output: &mut impl Write, message: &Json) -> io::Result<()> {
+    let body = message.to_string();
+    write!(output, "Content-Length: {}\r\n\r\n{body}", body.len())?;
+    return output.flush()
+}
+
+// Answers LSP messages from `input` until the client sends `exit` or the input ends
+pub fn serve(input: &mut impl BufRead, output: &mut impl Write) -> io::Result<()> {
+    let mut documents: HashMap<String, String> = HashMap::new();
+
+    while let Some(message) = read_message(input)? {
+        let method = message["method"].as_str().unwrap_or_default();
+        let params = &message["params"];
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+
+        match method {
+            "exit" => return Ok(()),
+
+            "textDocument/didOpen" | "textDocument/didChange" => {
+                // Documents are always synced in full
+                let text = match method {
+                    "textDocument/didOpen" => &params["textDocument"]["text"],
+                    _ => &params["contentChanges"][0]["text"],
+                };
+                let text = text.as_str().unwrap_or_default().to_string();
+                publish_diagnostics(output, uri, &diagnostics(&text))?;
+                documents.insert(uri.to_string(), text);
+            }
+
+            "textDocument/didClose" => {
+                documents.remove(uri);
+                publish_diagnostics(output, uri, &[])?;
+            }
+
+            _ => {}
+        }
+
+        // Requests need a response, notifications (and responses from the client) do not
+        let Some(id) = message.get("id").filter(|_| !method.is_empty()) else {
+            continue;
+        };
+        let response = match respond(method, params, documents.get(uri)) {
+            Some(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+            None => json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": {"code": METHOD_NOT_FOUND, "message": format!("Unknown method `{method}`")},
+            }),
+        };
+        write_message(output, &response)?;
+    }
+
+    return Ok(())
+}
+
+fn respond(method: &str, params: &Json, text: Option<&String>) -> Option<Json> {
+    let uri = &params["textDocument"]["uri"];
+    let line = params["position"]["line"].as_u64().unwrap_or_default() as usize;
+    let character = params["position"]["character"].as_u64().unwrap_or_default() as usize;
+    let text = text.map_or("", String::as_str);
+    let word = word_at(text, line, character);
+
+    match method {
+        "initialize" => {
+            return Some(json!({
+                "capabilities": {
+                    "textDocumentSync": 1,
+                    "definitionProvider": true,
+                    "referencesProvider": true,
+                    "hoverProvider": true,
+                    "completionProvider": {},
+                    "documentSymbolProvider": true,
+                },
+                "serverInfo": {"name": "script-ll-lsp", "version": env!("CARGO_PKG_VERSION")},
+            }))
+        }
+
+        "shutdown" => return Some(Json::Null),
+
+        "textDocument/definition" => {
+            let definition = labels(text)
+                .into_iter()
+                .find(|(_, _, name)| Some(name) == word.as_ref());
+            return Some(definition.map_or(Json::Null, |(line, character, name)| {
+                return json!({"uri": uri, "range": range(line, character, name.chars().count())})
+            }))
+        }
+
+        "textDocument/references" => {
+            let Some(word) = word else {
+                return Some(json!([]))
+            };
+            let with_declaration = params["context"]["includeDeclaration"]
+                .as_bool()
+                .unwrap_or(true);
+            let declarations = labels(text)
+                .into_iter()
+                .map(|(line, character, _)| (line, character))
+                .collect::<Vec<(usize, usize)>>();
+
+            let references = occurrences(text, &word)
+                .into_iter()
+                .filter(|position| with_declaration || !declarations.contains(position))
+                .map(|(line, character)| {
+                    return json!({"uri": uri, "range": range(line, character, word.chars().count())})
+                })
+                .collect::<Vec<Json>>();
+            return Some(json!(references))
+        }
+
+        "textDocument/hover" => {
+            let command = word.as_deref().and_then(commands::find);
+            return Some(command.map_or(Json::Null, |command| {
+                let help = docs::command_help(command);
+                return json!({"contents": {"kind": "markdown", "value": format!("```text\n{help}```")}})
+            }))
+        }
+
+        "textDocument/completion" => {
+            let before = text.lines().nth(line).map_or(String::new(), |line| {
+                return line.chars().take(character).collect::<String>()
+            });
+            let prefix = before
+                .rsplit(char::is_whitespace)
+                .next()
+                .unwrap_or_default();
+
+            // The first word of a line is a command, later ones are completed as labels
+            let items = if before.trim_start() == prefix {
+                commands::complete(prefix)
+                    .into_iter()
+                    .map(|command| {
+                        return json!({
+                            "label": command.name,
+                            "kind": COMPLETION_FUNCTION,
+                            "detail": command.usage(),
+                            "documentation": command.help,
+                        })
+                    })
+                    .collect::<Vec<Json>>()
+            } else {
+                labels(text)
+                    .into_iter()
+                    .filter(|(_, _, name)| name.starts_with(prefix))
+                    .map(|(_, _, name)| return json!({"label": name, "kind": COMPLETION_REFERENCE}))
+                    .collect::<Vec<Json>>()
+            };
+            return Some(json!(items))
+        }
+
+        "textDocument/documentSymbol" => {
+            let symbols = labels(text)
+                .into_iter()
+                .map(|(line, character, name)| {
+                    let length = text
+                        .lines()
+                        .nth(line)
+                        .map_or(0, |line| line.chars().count());
+                    return json!({
+                        "name": name,
+                        "kind": SYMBOL_FUNCTION,
+                        "range": range(line, 0, length),
+                        "selectionRange": range(line, character, name.chars().count()),
+                    })
+                })
+                .collect::<Vec<Json>>();
+            return Some(json!(symbols))
+        }
+
+        _ => return None,
+    }
+}
+
+fn publish_diagnostics(output: &mut impl Write, uri: &str, diagnostics: &[Json]) -> io::Result<()> {
+    return write_message(
+        output,
+        &json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/publishDiagnostics",
+            "params": {"uri": uri, "diagnostics": diagnostics},
+        }),
+    )
+}
+
+// A range on one line. Characters are counted as chars, which matches UTF-16 for ASCII scripts
+fn range(line: usize, character: usize, length: usize) -> Json {
+    return json!({
+        "start": {"line": line, "character": character},
+        "end": {"line": line, "character": character + length},
+    })
+}
+
+// Errors from the lexer, or from the checker if the script could be parsed
+fn diagnostics(text: &str) -> Vec<Json> {
+    let errors = match crate::parse(text.to_string()) {
+        Ok(labels) => checker::check(&labels),
+        Err(error) => vec![error],
+    };
+    return errors
+        .iter()
+        .map(|error| {
+            let line = error.line_number.saturating_sub(1);
+            let length = text
+                .lines()
+                .nth(line)
+                .map_or(0, |line| line.chars().count());
+            let message = [vec![error.message.clone()], error.notes.clone()].concat();
+
+            return json!({
+                "range": range(line, 0, length),
+                "severity": SEVERITY_ERROR,
+                "source": "script-ll",
+                "message": message.join("\n"),
+            })
+        })
+        .collect()
+}
+
+// Where the text of a token is after `offset` bytes of it, as tokens may span several lines
+fn position_in(token: &Token, offset: usize) -> (usize, usize) {
+    let before = &token.text[..offset];
+    match before.rfind('\n') {
+        Some(newline) => {
+            return (
+                token.line + before.matches('\n').count(),
+                before[newline + 1..].chars().count(),
+            )
+        }
+        None => return (token.line, token.character + before.chars().count()),
+    }
+}
+
+// The name under the cursor, also inside a `{name}` placeholder. Comments have no names
+fn word_at(text: &str, line: usize, character: usize) -> Option<String> {
+    let in_comment = lexer::tokens(text).iter().any(|token| {
+        let end = position_in(token, token.text.len());
+        return token.kind == TokenKind::Comment
+            && (token.line, token.character) < (line, character)
+            && (line, character) <= end
+    });
+    if in_comment {
+        return None
+    }
+
+    let characters = text.lines().nth(line)?.chars().collect::<Vec<char>>();
+    let is_part = |c: &char| return !c.is_whitespace() && !['"', '{', '}', '#'].contains(c);
+
+    let mut start = character.min(characters.len());
+    while start > 0 && is_part(&characters[start - 1]) {
+        start -= 1;
+    }
+    let mut end = character.min(characters.len());
+    while end < characters.len() && is_part(&characters[end]) {
+        end += 1;
+    }
+
+    return (start < end).then(|| return characters[start..end].iter().collect())
+}
+
+// Words and strings, without spaces and comments
+fn code_tokens(text: &str) -> Vec<Token> {
+    return lexer::tokens(text)
+        .into_iter()
+        .filter(|token| matches!(token.kind, TokenKind::Word | TokenKind::String))
+        .collect()
+}
+
+// Every `label <name>` line with the position of the name
+fn labels(text: &str) -> Vec<(usize, usize, String)> {
+    let mut lines: BTreeMap<usize, Vec<Token>> = BTreeMap::new();
+    for token in code_tokens(text) {
+        lines.entry(token.line).or_default().push(token);
+    }
+
+    return lines
+        .into_values()
+        .filter_map(|tokens| match tokens.as_slice() {
+            [command, name] if command.text == "label" => {
+                return Some((name.line, name.character, name.text.clone()))
+            }
+            _ => return None,
+        })
+        .collect()
+}
+
+// Where `name` is used as an argument or in a `{name}` placeholder
+fn occurrences(text: &str, name: &str) -> Vec<(usize, usize)> {
+    let mut positions = Vec::new();
+    let placeholder = format!("{{{name}}}");
+
+    for token in code_tokens(text) {
+        if token.text == name {
+            positions.push((token.line, token.character));
+        } else if token.kind == TokenKind::String {
+            for (offset, _) in token.text.match_indices(&placeholder) {
+                positions.push(position_in(&token, offset + 1));
+            }
+        }
+    }
+
+    return positions
+}
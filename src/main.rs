@@ -6,132 +6,648 @@
     clippy::cargo
 )]
 
-use ast::{has_label, Label};
+use script_ll_2::ast::Label;
+use script_ll_2::checker;
+use script_ll_2::commands::{self, builtin_commands};
+use script_ll_2::docs;
+use script_ll_2::formatter;
+use script_ll_2::highlight;
+use script_ll_2::interpreter::Interpreter;
+use script_ll_2::limits::{Limits, LARGE_STACK_MAX_CALL_DEPTH, LARGE_STACK_SIZE};
+use script_ll_2::linter;
+use script_ll_2::permissions::{PathAccess, Permissions};
 
-use std::collections::HashMap;
 use std::env::args;
+use std::io;
+use std::path::{Path, PathBuf};
 
 use std::fs::{self};
 
 use std::process::{self};
+use std::thread;
+use std::time::Duration;
 
-pub mod ast;
-pub mod interpreter;
-pub mod lexer;
-
-use interpreter::Interpreter;
+// How often `--watch` looks for changes
+const WATCH_INTERVAL: Duration = Duration::from_millis(250);
 
 fn main() {
-    let mut arguments = args();
-    arguments.next().unwrap();
+    let cli = thread::Builder::new()
+        .stack_size(LARGE_STACK_SIZE)
+        .spawn(run_cli)
+        .expect("Could not start the interpreter thread");
+
+    if cli.join().is_err() {
+        process::exit(101);
+    }
+}
+
+fn run_cli() {
+    let mut arguments = args().skip(1).peekable();
+
+    // Without any permission flags scripts are trusted and may do everything
+    let mut permissions: Option<Permissions> = None;
+    let mut limits = Limits::large_stack();
+    while let Some(flag) = arguments.next_if(|argument| {
+        argument.starts_with("--allow-")
+            || argument == "--sandbox"
+            || argument.starts_with("--max-")
+            || argument.starts_with("--timeout")
+    }) {
+        if flag.starts_with("--max-") || flag.starts_with("--timeout") {
+            apply_limit_flag(&mut limits, &flag);
+            continue;
+        }
+        let permissions = permissions.get_or_insert_with(Permissions::none);
+        apply_permission_flag(permissions, &flag);
+    }
+    let permissions = permissions.unwrap_or_else(Permissions::all);
 
     match arguments.next() {
         Some(input_file) => {
             if input_file == "--h" {
+                if let Some(name) = arguments.next() {
+                    print_command_help(&name);
+                    return;
+                }
+
                 println!("[Help]");
-                println!("label <label_name>                              Creates a label");
-                println!("jmp <label_name>                                Jumps to a label");
-                println!("var <name> <value>                              Makes a variable (Note: Variables are global and are not limited to a label)");
-                println!("require <variable_name>                         Makes it necessary for variable <variable_name> to exist.");
-                println!("test_lt_eq <number1> <number2>                  Tests whether <number1> is less than or equal to <number2>");
-                println!("test_gt_eq <number1> <number2>                  Tests whether <number1> is greater than or equal to <number2>");
-                println!("test_lt <number1> <number2>                     Tests whether <number1> is less than <number2>");
-                println!("test_gt <number1> <number2>                     Tests whether <number1> is greater than <number2>");
-                println!("test_eq <value1> <value2>                       Tests whether <value1> is equal to <value2>");
-                println!("cmd_eq <value1> <value2> <command> <args>...    Executes <command> with arguments <args> if <value1> is equal to <value2>");
-                println!("cmt <anything>...                               A comment. Ignored by the interpreter");
+                print_commands();
+                println!();
+                println!("[Numbers]");
+                println!("Integers: 42, -7, 1_000_000, 0xFF, 0b1010     Floats: 2.5, -0.5, 1e-3, 6.02e23");
+                println!("Integer arithmetic fails on overflow, anything involving a Float gives a Float.");
+                println!();
+                println!("[Permissions]");
+                println!("By default a script may do everything. Pass any of these flags before the script to allow only what is listed:");
+                println!("--sandbox                                       Allows nothing");
+                println!("--allow-read[=<path>,...]                       Allows reading files (only inside <path>s, if given)");
+                println!("--allow-write[=<path>,...]                      Allows writing files (only inside <path>s, if given)");
+                println!("--allow-env                                     Allows reading and changing environment variables");
+                println!(
+                    "--allow-run                                     Allows running other programs"
+                );
+                println!("--allow-net                                     Allows network access");
+                println!("--allow-all                                     Allows everything");
+                println!();
+                println!("[Limits]");
+                println!("Pass any of these flags before the script to stop it once it goes over a limit:");
+                println!("--max-instructions=<count>                      Stops after running <count> commands");
+                println!("--timeout=<seconds>                             Stops after <seconds> (e.g. 0.5) have passed");
+                println!("--max-depth=<depth>                             Stops once <depth> jumps are nested (Default: {LARGE_STACK_MAX_CALL_DEPTH})");
+                println!("--max-memory=<bytes>                            Stops once the variables hold more than about <bytes>");
+                println!("                                                (try cannot catch these errors)");
+                println!();
+                println!("[Arguments]");
+                println!("Arguments after the script are stored as Strings in the list ARGV, and their number in ARGC.");
+                println!("[Example] script-ll-2 greet.ll Ann Bo    (ARGC is 2, `get name ARGV 0` stores \"Ann\" in name)");
+                println!();
+                println!("[Strings]");
+                println!("\"...\" strings end on the same line, \"\"\"...\"\"\" strings may span several lines and keep tabs and line breaks.");
+                println!("When the closing \"\"\" is on its own line, its indentation is removed from every line of the string.");
+                println!("`\\n` is a line break and `\\\\` a backslash. Text read from files or commands is never unescaped or interpolated.");
+                println!();
+                println!("[Comments]");
+                println!("`# ...` comments out the rest of a line and `#[ ... ]#` everything in between, even across lines.");
+                println!("Both are removed before the script runs, `cmt` still works as a comment command.");
+                println!();
+                println!("[Input]");
+                println!("Use `-` instead of a file to read the script from stdin, or `-e \"<source_code>\"` to run code directly.");
+                println!(
+                    "Scripts may start with `#!/usr/bin/env script-ll-2` to be run as executables."
+                );
+                println!();
+                println!("[Testing]");
+                println!("Run with `--test <source_code_file>` to run every label named test_* on its own and print a summary.");
+                println!("Run with `--check <source_code_file>` to find mistakes such as unknown commands or labels without running the script.");
+                println!();
+                println!("[Linting]");
+                println!("Run with `--lint <source_code_file>` to find likely bugs, `--lint --json` prints them as JSON.");
+                println!("Rules can be turned off with `--disable=<rule>,<rule>`, or on one line with `# lint: allow(<rule>)`.");
+                for rule in &linter::RULES {
+                    println!("{:<47} {}", rule.name, rule.description);
+                }
+                println!();
+                println!("[Formatting]");
+                println!("Run with `--fmt <source_code_file>...` to rewrite scripts with consistent indentation, spacing and blank lines.");
+                println!("Run with `--fmt --check <source_code_file>...` to only list the scripts which need formatting.");
+                println!();
+                println!("[Watching]");
+                println!("Run with `--watch <source_code_file> [arguments]...` to run a script again every time it is saved.");
+                println!();
+                println!("[Documentation]");
+                println!("Run with `--h <command>` to see the parameters, examples and errors of a command.");
+                println!("Run with `--doc` (or `--doc html`) to print the reference of every command as Markdown (or HTML).");
+                println!("Run with `--highlight html <source_code_file>` to print a script as highlighted HTML, `--highlight css` prints its colours.");
+                println!("Run with `--grammar` to print a TextMate grammar for editors, it is also in `editors/script-ll.tmLanguage.json`.");
                 println!();
                 println!("[Basic Hello World script]");
                 println!("label .ENTRY");
                 println!("    print \"Hello World\"");
                 return;
             }
-            match fs::read_to_string(input_file.clone()) {
-                Ok(code) => {
-                    let lexed_code = lexer::lex(code);
-
-                    let _variables: HashMap<String, String> = HashMap::new();
-                    let mut labels: Vec<Label> = vec![];
-                    let mut current_label: String = String::new();
-                    let mut label_code: Vec<(usize, lexer::Line)> = Vec::new();
-
-                    for (line_number, line) in lexed_code.iter().enumerate() {
-                        let line_number = line_number + 1;
-                        let line: Vec<String> = line.clone().0;
-                        if line.is_empty() {
-                            continue;
-                        }
-                        let string_line = line.clone().join(" ");
-
-                        let command: String = line[0].clone();
-                        let args: Vec<String> = line[1..].to_vec().clone();
-                        let args_len = args.len();
-
-                        match command.clone().as_str() {
-                            "label" => {
-                                if args_len != 1 {
-                                    println!("[Error] Expected 1 argument, but got {args_len}");
-                                    println!("[Code]");
-                                    println!("{line_number} | {string_line}");
-                                    println!("[Usage] label <label_name>");
-                                    process::exit(1);
-                                }
-
-                                labels.push(Label {
-                                    label_name: current_label.clone(),
-                                    label_code,
-                                });
-                                label_code = Vec::new();
-
-                                let label_name = args[0].clone();
-                                current_label = label_name.clone();
-
-                                if has_label(labels.clone(), label_name.clone()) {
-                                    println!("[Error] Label `{label_name}` already exists.");
-                                    println!("[Code]");
-                                    println!("{line_number} | {string_line}");
-                                    println!("[Help] Do not use an existing label name.");
-                                    process::exit(1);
-                                }
-                            }
-
-                            _ => {
-                                label_code.push((line_number, lexer::Line(line.clone())));
-                            }
-                        }
+            if input_file == "--test" {
+                match arguments.next() {
+                    Some(test_file) => {
+                        let script_dir = Path::new(&test_file).parent().unwrap_or(Path::new(""));
+                        run_tests(&parse_source(&test_file), script_dir, &permissions, &limits);
+                    }
+                    None => {
+                        let program: Vec<String> = args().collect();
+                        println!("[Usage] {} --test <source_code_file>", program[0]);
+                        process::exit(1);
+                    }
+                }
+                return;
+            }
 
-                        if line_number == lexed_code.len() {
-                            labels.push(Label {
-                                label_name: current_label.clone(),
-                                label_code,
-                            });
-                            label_code = Vec::new();
+            if input_file == "--doc" {
+                match arguments.next().as_deref() {
+                    None | Some("markdown") => print!("{}", docs::markdown()),
+                    Some("html") => print!("{}", docs::html()),
+                    Some(format) => {
+                        eprintln!("[Error] Unknown documentation format `{format}`");
+                        eprintln!("[Help] Use `--doc markdown` or `--doc html`");
+                        process::exit(1);
+                    }
+                }
+                return;
+            }
+
+            if input_file == "--highlight" {
+                match (arguments.next().as_deref(), arguments.next()) {
+                    (Some("html"), Some(file)) => match highlight::html(&read_source(&file)) {
+                        Ok(html) => print!("{html}"),
+
+                        Err(error) => {
+                            eprintln!("{error}");
+                            process::exit(1);
                         }
+                    },
+                    (Some("css"), None) => print!("{}", highlight::STYLESHEET),
+                    _ => {
+                        let program: Vec<String> = args().collect();
+                        println!("[Usage] {} --highlight html <source_code_file>", program[0]);
+                        println!("[Usage] {} --highlight css", program[0]);
+                        process::exit(1);
                     }
+                }
+                return;
+            }
 
-                    if has_label(labels.clone(), ".ENTRY".to_string()) {
-                        let mut interpreter = Interpreter::new(labels);
-                        interpreter.interpret();
-                    } else {
-                        println!("[Error] Label `.ENTRY` does not exist. (.ENTRY is the main entry point of the script.");
-                        println!("[Help] Add a label named `.ENTRY` using `label .ENTRY`");
+            if input_file == "--grammar" {
+                print!("{}", highlight::textmate_grammar());
+                return;
+            }
+
+            if input_file == "--watch" {
+                match arguments.next() {
+                    Some(watch_file) if watch_file != "-" => {
+                        watch(
+                            &watch_file,
+                            &arguments.collect::<Vec<String>>(),
+                            &permissions,
+                            &limits,
+                        );
+                    }
+                    _ => {
+                        let program: Vec<String> = args().collect();
+                        println!(
+                            "[Usage] {} --watch <source_code_file> [arguments]...",
+                            program[0]
+                        );
                         process::exit(1);
                     }
                 }
+                return;
+            }
 
-                Err(e) => {
-                    println!("[Error] Could not open file `{input_file}`");
-                    println!("[Reason] {e}");
+            if input_file == "--fmt" {
+                let check = arguments.next_if_eq("--check").is_some();
+                let files: Vec<String> = arguments.collect();
+                if files.is_empty() {
+                    let program: Vec<String> = args().collect();
+                    println!(
+                        "[Usage] {} --fmt [--check] <source_code_file>...",
+                        program[0]
+                    );
                     process::exit(1);
                 }
+                format_files(&files, check);
+                return;
+            }
+
+            if input_file == "--lint" {
+                let mut json = false;
+                let mut disabled: Vec<String> = Vec::new();
+                while let Some(flag) = arguments.next_if(|argument| argument.starts_with("--")) {
+                    match flag.split_once('=') {
+                        None if flag == "--json" => json = true,
+                        Some(("--disable", rules)) => {
+                            disabled.extend(rules.split(',').map(ToString::to_string));
+                        }
+                        _ => {
+                            eprintln!("[Error] Unknown flag `{flag}`");
+                            eprintln!("[Help] Use `--json` or `--disable=<rule>,<rule>`");
+                            process::exit(1);
+                        }
+                    }
+                }
+
+                match arguments.next() {
+                    Some(lint_file) => lint(&lint_file, &disabled, json),
+                    None => {
+                        let program: Vec<String> = args().collect();
+                        println!(
+                            "[Usage] {} --lint [--json] [--disable=<rule>,<rule>] <source_code_file>",
+                            program[0]
+                        );
+                        process::exit(1);
+                    }
+                }
+                return;
+            }
+
+            if input_file == "--check" {
+                match arguments.next() {
+                    Some(check_file) => check(&parse_source(&check_file)),
+                    None => {
+                        let program: Vec<String> = args().collect();
+                        println!("[Usage] {} --check <source_code_file>", program[0]);
+                        process::exit(1);
+                    }
+                }
+                return;
             }
+
+            // `-e <code>` runs inline code, relative paths then start in the current directory
+            let (code, script_dir) = if input_file == "-e" {
+                match arguments.next() {
+                    Some(code) => (code, Path::new("")),
+                    None => {
+                        let program: Vec<String> = args().collect();
+                        println!("[Usage] {} -e <source_code> [arguments]...", program[0]);
+                        process::exit(1);
+                    }
+                }
+            } else {
+                (
+                    read_source(&input_file),
+                    Path::new(&input_file).parent().unwrap_or(Path::new("")),
+                )
+            };
+            let script_args: Vec<String> = arguments.collect();
+            let configure = |interpreter: &mut Interpreter| {
+                interpreter.set_script_dir(script_dir.to_path_buf());
+                interpreter.set_args(script_args);
+                interpreter.set_permissions(permissions);
+                interpreter.set_limits(limits);
+            };
+            process::exit(script_ll_2::run(code, configure, &mut io::stderr()));
         }
 
         None => {
             let program: Vec<String> = args().collect();
-            println!("[Usage] {} <source_code_file>", program[0]);
+            println!("[Usage] {} <source_code_file> [arguments]...", program[0]);
+            println!(
+                "[Usage] {} - [arguments]...  (reads the source code from stdin)",
+                program[0]
+            );
+            println!("[Usage] {} -e <source_code> [arguments]...", program[0]);
             println!("[Example] {} examples/tutorial.ll", program[0]);
             println!("[For help regarding the language] {} --h", program[0]);
         }
     }
 }
+
+// Handles `--sandbox`, `--allow-env`, `--allow-read=<path>,<path>`, ...
+fn apply_permission_flag(permissions: &mut Permissions, flag: &str) {
+    let (name, paths) = match flag.split_once('=') {
+        Some((name, paths)) => (name, Some(paths)),
+        None => (flag, None),
+    };
+
+    let path_access = match paths {
+        Some(paths) => PathAccess::Only(
+            paths
+                .split(',')
+                .map(PathBuf::from)
+                .collect::<Vec<PathBuf>>(),
+        ),
+        None => PathAccess::All,
+    };
+
+    match (name, paths) {
+        ("--sandbox", None) => {}
+        ("--allow-all", None) => *permissions = Permissions::all(),
+        ("--allow-read", _) => permissions.read = merge(&permissions.read, path_access),
+        ("--allow-write", _) => permissions.write = merge(&permissions.write, path_access),
+        ("--allow-env", None) => permissions.env = true,
+        ("--allow-run", None) => permissions.run = true,
+        ("--allow-net", None) => permissions.net = true,
+
+        _ => {
+            eprintln!("[Error] Unknown flag `{flag}`");
+            eprintln!("[Help] Run with `--h` to see every flag.");
+            process::exit(1);
+        }
+    }
+}
+
+// Lists every built-in command, with the note of a category after its last command
+fn print_commands() {
+    let commands = builtin_commands();
+    for (index, command) in commands.iter().enumerate() {
+        println!("{}", command.help_line());
+
+        let is_last_of_category = commands
+            .get(index + 1)
+            .is_none_or(|next| next.category != command.category);
+        if let (true, Some(note)) = (is_last_of_category, command.category.note()) {
+            println!("{:<47} {note}", "");
+        }
+    }
+}
+
+// `--h <command>`, the detailed help of one command
+fn print_command_help(name: &str) {
+    match commands::find(name) {
+        Some(command) => print!("{}", docs::command_help(command)),
+
+        None => {
+            eprintln!("[Error] Unknown command `{name}`");
+            eprintln!("[Help] Run with `--h` to see every command.");
+            process::exit(1);
+        }
+    }
+}
+
+// Handles `--max-instructions=<count>`, `--timeout=<seconds>`, `--max-depth=<depth>` and
+// `--max-memory=<bytes>`
+fn apply_limit_flag(limits: &mut Limits, flag: &str) {
+    let (name, value) = flag.split_once('=').unwrap_or((flag, ""));
+
+    let applied = match name {
+        "--max-instructions" => value
+            .parse()
+            .map(|count| limits.max_instructions = Some(count))
+            .is_ok(),
+        "--max-depth" => value
+            .parse()
+            .map(|depth| limits.max_call_depth = Some(depth))
+            .is_ok(),
+        "--max-memory" => value
+            .parse()
+            .map(|bytes| limits.max_memory = Some(bytes))
+            .is_ok(),
+        "--timeout" => match value.parse::<f64>().map(Duration::try_from_secs_f64) {
+            Ok(Ok(timeout)) => {
+                limits.timeout = Some(timeout);
+                true
+            }
+            _ => false,
+        },
+
+        _ => {
+            eprintln!("[Error] Unknown flag `{flag}`");
+            eprintln!("[Help] Run with `--h` to see every flag.");
+            process::exit(1);
+        }
+    };
+
+    if !applied {
+        eprintln!("[Error] Invalid value `{value}` for flag `{name}`");
+        eprintln!("[Help] Use a positive number, e.g. `{name}=10`");
+        process::exit(1);
+    }
+}
+
+// Repeating a flag such as `--allow-read=a --allow-read=b` allows both paths
+fn merge(old: &PathAccess, new: PathAccess) -> PathAccess {
+    match (old, new) {
+        (PathAccess::Only(old), PathAccess::Only(new)) => {
+            return PathAccess::Only([old.clone(), new].concat())
+        }
+        _ => return PathAccess::All,
+    }
+}
+
+// `-` reads the script from stdin
+fn read_source(input_file: &str) -> String {
+    let code = if input_file == "-" {
+        io::read_to_string(io::stdin())
+    } else {
+        fs::read_to_string(input_file)
+    };
+
+    match code {
+        Ok(code) => return code,
+
+        Err(e) => {
+            eprintln!("[Error] Could not open file `{input_file}`");
+            eprintln!("[Reason] {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn parse_source(input_file: &str) -> Vec<Label> {
+    match script_ll_2::parse(read_source(input_file)) {
+        Ok(labels) => return labels,
+
+        Err(error) => {
+            eprintln!("{error}");
+            process::exit(1);
+        }
+    }
+}
+
+// Prints every mistake the checker finds and exits with 1 if there are any
+fn check(labels: &[Label]) {
+    let errors = checker::check(labels);
+
+    for error in &errors {
+        println!("{error}");
+        println!();
+    }
+
+    match errors.len() {
+        0 => println!("[Check] No problems found."),
+        1 => println!("[Check] 1 problem found."),
+        count => println!("[Check] {count} problems found."),
+    }
+
+    if !errors.is_empty() {
+        process::exit(1);
+    }
+}
+
+// Runs the script again whenever it changes, until stopped with Ctrl+C. Files are polled so that
+// no file system notification service is needed
+fn watch(input_file: &str, script_args: &[String], permissions: &Permissions, limits: &Limits) {
+    let script_dir = Path::new(input_file).parent().unwrap_or(Path::new(""));
+    let mut last_code: Option<String> = None;
+
+    loop {
+        let code = fs::read_to_string(input_file).ok();
+        if code == last_code {
+            thread::sleep(WATCH_INTERVAL);
+            continue;
+        }
+        last_code.clone_from(&code);
+
+        // Clears the screen and moves the cursor to the top
+        print!("\x1b[2J\x1b[H");
+        println!("[Watch] Running `{input_file}`, stop with Ctrl+C");
+        println!();
+
+        let exit_code = match code {
+            Some(code) => {
+                let configure = |interpreter: &mut Interpreter| {
+                    interpreter.set_script_dir(script_dir.to_path_buf());
+                    interpreter.set_args(script_args.to_vec());
+                    interpreter.set_permissions(permissions.clone());
+                    interpreter.set_limits(limits.clone());
+                };
+                script_ll_2::run(code, configure, &mut io::stderr())
+            }
+
+            None => {
+                eprintln!("[Error] Could not open file `{input_file}`");
+                1
+            }
+        };
+
+        println!();
+        println!("[Watch] Exited with code {exit_code}, waiting for changes...");
+    }
+}
+
+// Prints the warnings of every enabled lint rule and exits with 1 if there are any
+fn lint(input_file: &str, disabled: &[String], json: bool) {
+    if let Some(rule) = disabled
+        .iter()
+        .find(|rule| linter::find_rule(rule).is_none())
+    {
+        let rules = linter::RULES.map(|rule| rule.name);
+        eprintln!("[Error] Unknown lint rule `{rule}`");
+        eprintln!("[Help] The rules are {}", rules.join(", "));
+        process::exit(1);
+    }
+
+    let lints = match linter::lint(&read_source(input_file), disabled) {
+        Ok(lints) => lints,
+
+        Err(error) => {
+            eprintln!("{error}");
+            process::exit(1);
+        }
+    };
+
+    if json {
+        println!("{}", linter::to_json(&lints));
+    } else {
+        for lint in &lints {
+            println!("{lint}");
+            println!();
+        }
+
+        match lints.len() {
+            0 => println!("[Lint] No problems found."),
+            1 => println!("[Lint] 1 warning."),
+            count => println!("[Lint] {count} warnings."),
+        }
+    }
+
+    if !lints.is_empty() {
+        process::exit(1);
+    }
+}
+
+// `--fmt` rewrites every file which is not formatted yet, `--fmt --check` only lists them and
+// exits with 1 if there are any. `-` formats stdin to stdout
+fn format_files(files: &[String], check: bool) {
+    let mut unformatted = 0;
+
+    for file in files {
+        let code = read_source(file);
+        let formatted = match formatter::format(&code) {
+            Ok(formatted) => formatted,
+
+            Err(error) => {
+                eprintln!("[File] {file}");
+                eprintln!("{error}");
+                process::exit(1);
+            }
+        };
+
+        if file == "-" && !check {
+            print!("{formatted}");
+            continue;
+        }
+        if formatted == code {
+            continue;
+        }
+        unformatted += 1;
+
+        if check {
+            println!("[Format] `{file}` needs formatting");
+        } else if let Err(e) = fs::write(file, formatted) {
+            eprintln!("[Error] Could not write file `{file}`");
+            eprintln!("[Reason] {e}");
+            process::exit(1);
+        } else {
+            println!("[Format] Formatted `{file}`");
+        }
+    }
+
+    if check && unformatted > 0 {
+        println!("[Format] Run with `--fmt` to format them.");
+        process::exit(1);
+    }
+}
+
+// Runs every `test_*` label in a fresh interpreter and exits with 1 if any of them fail
+fn run_tests(labels: &[Label], script_dir: &Path, permissions: &Permissions, limits: &Limits) {
+    let tests = labels
+        .iter()
+        .filter(|label| label.label_name.starts_with("test_"))
+        .map(|label| label.label_name.clone())
+        .collect::<Vec<String>>();
+
+    if tests.is_empty() {
+        println!("[Error] No tests found.");
+        println!("[Help] Add a label whose name starts with `test_`, e.g. `label test_addition`");
+        process::exit(1);
+    }
+
+    let mut failed: Vec<String> = Vec::new();
+
+    for test in &tests {
+        let mut interpreter = Interpreter::new(labels.to_vec());
+        interpreter.set_script_dir(script_dir.to_path_buf());
+        interpreter.set_permissions(permissions.clone());
+        interpreter.set_limits(limits.clone());
+
+        match interpreter.interpret_from(test.clone()) {
+            Ok(()) => println!("[Test] {test} ... ok"),
+
+            Err(error) => {
+                println!("[Test] {test} ... FAILED");
+                println!("{error}");
+                failed.push(test.clone());
+            }
+        }
+    }
+
+    println!();
+    println!(
+        "[Summary] {} passed, {} failed",
+        tests.len() - failed.len(),
+        failed.len()
+    );
+
+    if !failed.is_empty() {
+        println!("[Failed] {}", failed.join(", "));
+        process::exit(1);
+    }
+}
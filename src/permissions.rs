@@ -0,0 +1,128 @@
+use std::{
+    env,
+    path::{Component, Path, PathBuf},
+};
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Capability {
+    Read,
+    Write,
+    Env,
+    Run,
+    Net,
+}
+
+// The names used in `--allow-<name>` and in permission errors
+impl std::fmt::Display for Capability {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Read => write!(f, "read"),
+            Self::Write => write!(f, "write"),
+            Self::Env => write!(f, "env"),
+            Self::Run => write!(f, "run"),
+            Self::Net => write!(f, "net"),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum PathAccess {
+    All,
+    // Only paths inside these directories (or these exact files), empty means no access
+    Only(Vec<PathBuf>),
+}
+
+impl PathAccess {
+    #[must_use]
+    pub fn allows(&self, path: &Path) -> bool {
+        match self {
+            Self::All => return true,
+            Self::Only(roots) => {
+                let path = resolve(path);
+                return roots.iter().any(|root| path.starts_with(resolve(root)))
+            }
+        }
+    }
+}
+
+// What a script is allowed to do outside of the interpreter
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Permissions {
+    pub read: PathAccess,
+    pub write: PathAccess,
+    pub env: bool,
+    pub run: bool,
+    pub net: bool,
+}
+
+impl Permissions {
+    #[must_use]
+    pub const fn all() -> Self {
+        return Self {
+            read: PathAccess::All,
+            write: PathAccess::All,
+            env: true,
+            run: true,
+            net: true,
+        }
+    }
+
+    // Used for untrusted scripts, everything has to be allowed explicitly
+    #[must_use]
+    pub const fn none() -> Self {
+        return Self {
+            read: PathAccess::Only(Vec::new()),
+            write: PathAccess::Only(Vec::new()),
+            env: false,
+            run: false,
+            net: false,
+        }
+    }
+
+    // `path` is only needed for `Read` and `Write`
+    #[must_use]
+    pub fn allows(&self, capability: Capability, path: Option<&Path>) -> bool {
+        match capability {
+            Capability::Read => return path.is_some_and(|path| self.read.allows(path)),
+            Capability::Write => return path.is_some_and(|path| self.write.allows(path)),
+            Capability::Env => return self.env,
+            Capability::Run => return self.run,
+            Capability::Net => return self.net,
+        }
+    }
+}
+
+impl Default for Permissions {
+    fn default() -> Self {
+        return Self::all()
+    }
+}
+
+// Makes a path absolute and removes `..` so it cannot escape a root it seems to be inside of
+fn resolve(path: &Path) -> PathBuf {
+    if let Ok(path) = path.canonicalize() {
+        return path;
+    }
+
+    // Files which do not exist yet (e.g. for `write_file`) are resolved through their directory
+    if let (Some(parent), Some(file_name)) = (path.parent(), path.file_name()) {
+        if let Ok(parent) = parent.canonicalize() {
+            return parent.join(file_name);
+        }
+    }
+
+    let path = env::current_dir().unwrap_or_default().join(path);
+    let mut resolved = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            Component::CurDir => {}
+            _ => resolved.push(component),
+        }
+    }
+
+    return resolved
+}
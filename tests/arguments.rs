@@ -0,0 +1,21 @@
+use script_ll_2::interpreter::Interpreter;
+
+#[test]
+fn exposes_arguments_as_argc_and_argv() {
+    let code = "label .ENTRY
+    assert_eq ARGC 2
+    get first ARGV 0
+    assert_eq first \"--name\"
+    get second ARGV 1
+    typeof kind second
+    assert_eq kind \"String\"
+    assert_eq second \"Ann {{Bo}}\"";
+    let mut stderr = Vec::new();
+
+    let configure = |interpreter: &mut Interpreter| {
+        interpreter.set_args(vec!["--name".to_string(), "Ann {Bo}".to_string()]);
+    };
+    let exit_code = script_ll_2::run(code.to_string(), configure, &mut stderr);
+
+    assert_eq!(exit_code, 0, "{}", String::from_utf8(stderr).unwrap());
+}
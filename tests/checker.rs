@@ -0,0 +1,64 @@
+use script_ll_2::{checker, commands, error::ErrorKind};
+
+fn check(code: &str) -> Vec<(ErrorKind, String, usize)> {
+    let labels = script_ll_2::parse(code.to_string()).unwrap();
+    checker::check(&labels)
+        .into_iter()
+        .map(|error| (error.kind, error.message, error.line_number))
+        .collect()
+}
+
+#[test]
+fn accepts_valid_scripts() {
+    let code = "label .ENTRY\n    var x 1\n    list_new items\n    list_new more 1 2 3\n    map_get y m \"k\" 0\n    cmd_eq x 1 jmp done\nlabel done\n    cmt all good";
+    assert_eq!(check(code), Vec::new());
+}
+
+#[test]
+fn finds_mistakes_without_running() {
+    let code = "label .ENTRY\n    prnt \"hi\"\n    add x 1\n    jmp nowhere\n    push 5 1\n    cmd_eq 1 1 print\n    throw \"never reached, but still fine\"";
+
+    assert_eq!(
+        check(code),
+        vec![
+            (ErrorKind::Syntax, "Unknown command `prnt`".to_string(), 2),
+            (
+                ErrorKind::Arguments,
+                "Expected exactly 3 arguments, found 2".to_string(),
+                3
+            ),
+            (
+                ErrorKind::Label,
+                "Label `nowhere` does not exist.".to_string(),
+                4
+            ),
+            (
+                ErrorKind::Type,
+                "Expected `list_name` to be a variable name, not `5`".to_string(),
+                5
+            ),
+            (
+                ErrorKind::Arguments,
+                "Expected exactly 1 argument, found 0".to_string(),
+                6
+            ),
+        ]
+    );
+}
+
+#[test]
+fn completes_command_names() {
+    let names = commands::complete("map_")
+        .iter()
+        .map(|command| command.name.clone())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        names,
+        vec!["map_new", "map_set", "map_get", "map_has", "map_del", "map_keys", "map_len"]
+    );
+    assert_eq!(
+        commands::find("map_get").unwrap().usage(),
+        "map_get <destination> <map_name> <key> [default]"
+    );
+}
@@ -0,0 +1,105 @@
+use std::io::{BufRead, BufReader, Write};
+use std::process::{self, Command, Stdio};
+use std::sync::mpsc;
+use std::time::Duration;
+use std::{env, fs, thread};
+
+const BINARY: &str = env!("CARGO_BIN_EXE_script-ll-2");
+
+#[test]
+fn runs_inline_code() {
+    let output = Command::new(BINARY)
+        .args(["-e", "label .ENTRY\n    print \"inline {ARGV}\"", "a"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "inline [\"a\"]\n"
+    );
+}
+
+#[test]
+fn reads_source_code_from_stdin() {
+    let mut child = Command::new(BINARY)
+        .args(["-", "a", "b"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"#!/usr/bin/env script-ll-2\nlabel .ENTRY\n    print \"stdin {ARGC}\"")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "stdin 2\n");
+}
+
+#[test]
+fn checks_formatting() {
+    for (code, formatted) in [
+        ("label .ENTRY\n  print  1\n", false),
+        ("label .ENTRY\n    print 1\n", true),
+    ] {
+        let mut child = Command::new(BINARY)
+            .args(["--fmt", "--check", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(code.as_bytes())
+            .unwrap();
+        let output = child.wait_with_output().unwrap();
+
+        assert_eq!(output.status.success(), formatted, "{code}");
+    }
+}
+
+#[test]
+fn watch_runs_the_script_again_after_changes() {
+    let script = env::temp_dir().join(format!("script_ll_watch_{}.ll", process::id()));
+    fs::write(&script, "label .ENTRY\n    print \"first\"").unwrap();
+
+    let mut child = Command::new(BINARY)
+        .arg("--watch")
+        .arg(&script)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let (sender, receiver) = mpsc::channel();
+    let stdout = child.stdout.take().unwrap();
+    thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            if sender.send(line.unwrap()).is_err() {
+                return;
+            }
+        }
+    });
+    let wait_for = |expected: &str| loop {
+        let line = receiver.recv_timeout(Duration::from_secs(10)).unwrap();
+        if line.contains(expected) {
+            return;
+        }
+    };
+
+    wait_for("first");
+    wait_for("Exited with code 0");
+    fs::write(&script, "label .ENTRY\n    prnt \"second\"").unwrap();
+    wait_for("Exited with code 1");
+    fs::write(&script, "label .ENTRY\n    print \"third\"").unwrap();
+    wait_for("third");
+
+    child.kill().unwrap();
+    child.wait().unwrap();
+    fs::remove_file(&script).unwrap();
+}
@@ -0,0 +1,15 @@
+use script_ll_2::interpreter::Interpreter;
+
+// Runs `code` with `configure` setting up the interpreter, and returns the exit code and what the
+// script wrote to stderr. Its output is dropped
+pub fn run_with(code: &str, configure: impl FnOnce(&mut Interpreter)) -> (i32, String) {
+    let mut stderr = Vec::new();
+
+    let configure = |interpreter: &mut Interpreter| {
+        interpreter.set_output(Box::new(Vec::new()));
+        configure(interpreter);
+    };
+    let exit_code = script_ll_2::run(code.to_string(), configure, &mut stderr);
+
+    (exit_code, String::from_utf8(stderr).unwrap())
+}
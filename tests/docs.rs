@@ -0,0 +1,83 @@
+use script_ll_2::{checker, commands, docs, error::ErrorKind};
+
+#[test]
+fn documents_every_command() {
+    let markdown = docs::markdown();
+    let html = docs::html();
+
+    for command in commands::builtin_commands() {
+        assert!(
+            markdown.contains(&format!("### `{}`", command.name)),
+            "{}",
+            command.name
+        );
+        assert!(html.contains(&format!(
+            "<h3 id=\"{0}\"><code>{0}</code></h3>",
+            command.name
+        )));
+        assert!(
+            !command.examples.is_empty(),
+            "`{}` has no example",
+            command.name
+        );
+    }
+}
+
+#[test]
+fn examples_use_commands_correctly() {
+    for command in commands::builtin_commands() {
+        for example in &command.examples {
+            // Examples jump to labels they do not show, which is fine
+            let code = if example.starts_with("label ") {
+                example.clone()
+            } else {
+                format!("label example\n{example}")
+            };
+            let labels = script_ll_2::parse(code).unwrap();
+            let errors = checker::check(&labels)
+                .into_iter()
+                .filter(|error| error.kind != ErrorKind::Label)
+                .collect::<Vec<_>>();
+
+            assert!(
+                errors.is_empty(),
+                "example of `{}`: {errors:?}",
+                command.name
+            );
+        }
+    }
+}
+
+#[test]
+fn shows_help_for_one_command() {
+    let help = docs::command_help(commands::find("pop").unwrap());
+
+    assert!(help.starts_with("[Command] pop\n[Usage] pop <destination> <list_name>\n"));
+    assert!(help.contains("[Examples]\npop last primes\n"));
+    assert!(help.contains("IndexError: the list is empty"));
+}
+
+#[test]
+fn lists_errors_from_parameter_roles() {
+    let errors = |name: &str| {
+        commands::find(name)
+            .unwrap()
+            .all_errors()
+            .into_iter()
+            .map(|(kind, _)| kind)
+            .collect::<Vec<ErrorKind>>()
+    };
+
+    // `push` looks up its list, `map_new` makes its map and `label` makes its label
+    assert!(errors("push").contains(&ErrorKind::Variable));
+    assert!(!errors("map_new").contains(&ErrorKind::Variable));
+    assert!(!errors("label").contains(&ErrorKind::Variable));
+    assert_eq!(
+        errors("label")
+            .iter()
+            .filter(|kind| **kind == ErrorKind::Label)
+            .count(),
+        1
+    );
+    assert!(errors("cmt").is_empty());
+}
@@ -0,0 +1,85 @@
+use std::fs;
+use std::path::Path;
+
+use script_ll_2::formatter::format;
+use script_ll_2::lexer::lex;
+
+const MESSY: &str = "# Greets everyone
+  label .ENTRY   # entry
+
+
+\tprint   \"Hello  {{World}}\" # keeps   spacing
+      print \"\"\"
+   Dear all,
+   \"\"\"
+    jmp  bye
+label bye
+#[ a block
+   comment ]#
+  print \"Bye\"
+
+
+";
+
+const FORMATTED: &str = "# Greets everyone
+label .ENTRY # entry
+    print \"Hello  {{World}}\" # keeps   spacing
+    print \"\"\"
+   Dear all,
+   \"\"\"
+    jmp bye
+
+label bye
+    #[ a block
+   comment ]#
+    print \"Bye\"
+";
+
+// The lines of code without the blank ones, which the formatter may add or remove
+fn commands(code: &str) -> Vec<Vec<String>> {
+    return lex(code.to_string())
+        .unwrap()
+        .into_iter()
+        .map(|line| line.0)
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+#[test]
+fn formats_canonically() {
+    assert_eq!(format(MESSY).unwrap(), FORMATTED);
+}
+
+#[test]
+fn formatting_twice_changes_nothing() {
+    assert_eq!(format(FORMATTED).unwrap(), FORMATTED);
+}
+
+#[test]
+fn keeps_the_meaning_of_every_script() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let scripts = [root.join("examples"), root.join("tests/scripts")]
+        .into_iter()
+        .flat_map(|dir| fs::read_dir(dir).unwrap())
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "ll"));
+
+    for script in scripts {
+        let code = fs::read_to_string(&script).unwrap();
+        let Ok(formatted) = format(&code) else {
+            continue;
+        };
+
+        assert_eq!(
+            commands(&formatted),
+            commands(&code),
+            "{}",
+            script.display()
+        );
+    }
+}
+
+#[test]
+fn refuses_code_which_does_not_lex() {
+    assert!(format("label .ENTRY\n    print \"never ended").is_err());
+}
@@ -0,0 +1,51 @@
+use std::fs;
+use std::path::Path;
+
+use script_ll_2::commands::builtin_commands;
+use script_ll_2::highlight::{html, textmate_grammar};
+
+#[test]
+fn highlights_every_kind_of_token() {
+    let code = "label .ENTRY # start\n    var name \"<b>{name}</b> {{x}}\"\n    cmd_eq 0x1F true jmp done\n    bogus";
+
+    assert_eq!(
+        html(code).unwrap(),
+        "<pre class=\"script-ll\"><code>\
+<span class=\"ll-command\">label</span> <span class=\"ll-label\">.ENTRY</span> <span class=\"ll-comment\"># start</span>\n    \
+<span class=\"ll-command\">var</span> <span class=\"ll-variable\">name</span> \
+<span class=\"ll-string\">&quot;&lt;b&gt;<span class=\"ll-variable\">{name}</span>&lt;/b&gt; {{x}}&quot;</span>\n    \
+<span class=\"ll-command\">cmd_eq</span> <span class=\"ll-number\">0x1F</span> <span class=\"ll-bool\">true</span> \
+<span class=\"ll-command\">jmp</span> <span class=\"ll-label\">done</span>\n    \
+bogus</code></pre>\n"
+    );
+}
+
+#[test]
+fn refuses_code_which_does_not_lex() {
+    assert!(html("label .ENTRY\n    print \"never ended").is_err());
+}
+
+#[test]
+fn shipped_grammar_is_up_to_date() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("editors/script-ll.tmLanguage.json");
+    let grammar = textmate_grammar();
+
+    if std::env::var("BLESS").is_ok() {
+        fs::write(&path, &grammar).unwrap();
+    }
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        grammar,
+        "Run `BLESS=1 cargo test` to update the grammar"
+    );
+
+    for command in builtin_commands() {
+        assert!(
+            grammar.contains(&format!("|{}|", command.name))
+                || grammar.contains(&format!("({}|", command.name))
+                || grammar.contains(&format!("|{})", command.name)),
+            "{}",
+            command.name
+        );
+    }
+}
@@ -0,0 +1,154 @@
+mod common;
+
+use std::{cell::RefCell, rc::Rc};
+
+use script_ll_2::{
+    ast::Number,
+    error::ErrorKind,
+    host::{Argument, ArgumentKind, Context, Parameter},
+    interpreter::Interpreter,
+    permissions::{Capability, Permissions},
+};
+
+// Registers `record <value>`, which collects what the script passes to it
+fn run_with(code: &str, configure: impl FnOnce(&mut Interpreter)) -> (i32, String, Vec<Argument>) {
+    let recorded = Rc::new(RefCell::new(Vec::new()));
+
+    let record = Rc::clone(&recorded);
+    let (exit_code, stderr) = common::run_with(code, |interpreter| {
+        interpreter.register(
+            "record",
+            vec![Parameter::required("value", ArgumentKind::Any)],
+            move |_context: &mut Context, arguments: &[Argument]| {
+                record.borrow_mut().push(arguments[0].clone());
+                Ok(())
+            },
+        );
+        configure(interpreter);
+    });
+
+    let recorded = recorded.borrow().clone();
+    (exit_code, stderr, recorded)
+}
+
+fn register_double(interpreter: &mut Interpreter) {
+    interpreter.register(
+        "double",
+        vec![
+            Parameter::required("destination", ArgumentKind::Name),
+            Parameter::required("number", ArgumentKind::Number),
+            Parameter::optional("times", ArgumentKind::Number),
+        ],
+        |context, arguments| {
+            let number = arguments[1].as_number().unwrap().as_float();
+            let times = arguments
+                .get(2)
+                .map_or(1.0, |times| times.as_number().unwrap().as_float());
+            context.set_number(
+                arguments[0].as_str().unwrap(),
+                Number::Float(number * 2.0 * times),
+            );
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn passes_checked_arguments_and_writes_variables() {
+    let code =
+        "label .ENTRY\n    var x 4\n    double y x\n    record y\n    double y 1 3\n    record y";
+    let (exit_code, stderr, recorded) = run_with(code, register_double);
+
+    assert_eq!(exit_code, 0, "{stderr}");
+    assert_eq!(
+        recorded,
+        vec![
+            Argument::Number(Number::Float(8.0)),
+            Argument::Number(Number::Float(6.0))
+        ]
+    );
+}
+
+#[test]
+fn reports_wrong_number_of_arguments_with_usage() {
+    let code = "label .ENTRY\n    double y";
+    let (exit_code, stderr, _) = run_with(code, register_double);
+
+    assert_eq!(exit_code, 1);
+    assert!(
+        stderr.contains("Expected 2 or 3 arguments, found 1"),
+        "{stderr}"
+    );
+    assert!(
+        stderr.contains("[Usage] double <destination> <number> [times]"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn reports_wrong_argument_types() {
+    let code = "label .ENTRY\n    double y \"four\"";
+    let (exit_code, stderr, _) = run_with(code, register_double);
+
+    assert_eq!(exit_code, 1);
+    assert!(
+        stderr.contains("Expected `number` to be a Number, not a String"),
+        "{stderr}"
+    );
+
+    let code = "label .ENTRY\n    double 5 4";
+    let (exit_code, stderr, _) = run_with(code, register_double);
+
+    assert_eq!(exit_code, 1);
+    assert!(
+        stderr.contains("Expected `destination` to be a variable name, not `5`"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn reads_and_writes_variables_through_the_context() {
+    let code = "label .ENTRY
+    list_new items 1 \"two\"
+    var name \"you\"
+    var greeting \"hi {name}\"
+    copy_all
+    record items_copy
+    record greeting_copy
+    record missing_is_error";
+    let (exit_code, stderr, recorded) = run_with(code, |interpreter| {
+        interpreter.register("copy_all", Vec::new(), |context, _| {
+            let items = context.get("items")?;
+            let greeting = context.get("greeting")?;
+            context.set("items_copy", items.into_value());
+            context.set_string("greeting_copy", greeting.as_str().unwrap());
+            context.set_bool("missing_is_error", context.get("missing").is_err());
+            Ok(())
+        });
+    });
+
+    assert_eq!(exit_code, 0, "{stderr}");
+    assert!(matches!(&recorded[0], Argument::List(items) if items.len() == 2));
+    assert_eq!(recorded[1], Argument::String("hi you".to_string()));
+    assert_eq!(recorded[2], Argument::Bool(true));
+}
+
+#[test]
+fn host_errors_point_at_the_calling_line_and_respect_permissions() {
+    let code = "label .ENTRY\n    cmt\n    fetch";
+    let register_fetch = |interpreter: &mut Interpreter| {
+        interpreter.set_permissions(Permissions::none());
+        interpreter.register("fetch", Vec::new(), |context, _| {
+            context.check_permission(Capability::Net, None)?;
+            Err(context.error(ErrorKind::Io, "unreachable".to_string(), Vec::new()))
+        });
+    };
+    let (exit_code, stderr, _) = run_with(code, register_fetch);
+
+    assert_eq!(exit_code, 1);
+    assert!(
+        stderr.contains("Permission denied: `fetch` needs net access"),
+        "{stderr}"
+    );
+    assert!(stderr.contains("3 | fetch"), "{stderr}");
+}
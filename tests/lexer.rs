@@ -0,0 +1,54 @@
+use script_ll_2::lexer::{lex, tokens, Line, TokenKind};
+
+#[test]
+fn tokens_keep_every_character() {
+    let code = "label .ENTRY #[ a\n block ]# print\r\n\tprint \"\"\"\n  # not a comment\n  \"\"\" \"a # b\"";
+
+    assert_eq!(
+        tokens(code)
+            .into_iter()
+            .map(|token| token.text)
+            .collect::<String>(),
+        code
+    );
+}
+
+#[test]
+fn tokens_know_their_kind_and_position() {
+    let code = "print \"\"\"\n{x} # y\n\"\"\" #[ z\n]# done";
+    let tokens = tokens(code)
+        .into_iter()
+        .filter(|token| token.kind != TokenKind::Space)
+        .map(|token| (token.kind, token.line, token.character))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        tokens,
+        vec![
+            (TokenKind::Word, 0, 0),
+            (TokenKind::String, 0, 6),
+            (TokenKind::Comment, 2, 4),
+            (TokenKind::Word, 3, 3),
+        ]
+    );
+}
+
+#[test]
+fn commands_go_on_after_multi_line_strings() {
+    let code = "label .ENTRY\n    join text \"\"\"\n    a\n    \"\"\" \", \"\n    print text";
+
+    assert_eq!(
+        lex(code.to_string()).unwrap()[1..],
+        [
+            Line(vec![
+                "join".into(),
+                "text".into(),
+                "\"a\"".into(),
+                "\", \"".into()
+            ]),
+            Line(Vec::new()),
+            Line(Vec::new()),
+            Line(vec!["print".into(), "text".into()]),
+        ]
+    );
+}
@@ -0,0 +1,114 @@
+mod common;
+
+use std::thread;
+use std::time::Duration;
+
+use script_ll_2::limits::{Limits, DEFAULT_MAX_CALL_DEPTH, LARGE_STACK_SIZE};
+
+fn run_with(code: &str, limits: Limits) -> (i32, String) {
+    common::run_with(code, |interpreter| interpreter.set_limits(limits))
+}
+
+const ENDLESS_LOOP: &str =
+    "label .ENTRY\n    var i 0\n    jmp loop\nlabel loop\n    add i i 1\n    jmp loop";
+
+#[test]
+fn stops_after_instruction_budget() {
+    let limits = Limits {
+        max_instructions: Some(100),
+        max_call_depth: None,
+        ..Limits::none()
+    };
+    let (exit_code, stderr) = run_with(ENDLESS_LOOP, limits);
+
+    assert_eq!(exit_code, 1);
+    assert!(
+        stderr.contains("Instruction limit of 100 reached in label `loop`"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn stops_after_timeout() {
+    let limits = Limits {
+        timeout: Some(Duration::from_millis(50)),
+        ..Limits::large_stack()
+    };
+    // Nested loops over 100 items run 100 million commands without nesting more than a few jumps
+    let code = "label .ENTRY
+    list_new items
+    var n 0
+    jmp fill
+    foreach a items outer
+label fill
+    push items n
+    add n n 1
+    test_lt n 100
+    cmd_eq TEMP true jmp fill
+label outer
+    foreach b items middle
+label middle
+    foreach c items inner
+label inner
+    foreach d items spin
+label spin
+    cmt";
+    let (exit_code, stderr) = thread::Builder::new()
+        .stack_size(LARGE_STACK_SIZE)
+        .spawn(move || run_with(code, limits))
+        .unwrap()
+        .join()
+        .unwrap();
+
+    assert_eq!(exit_code, 1);
+    assert!(stderr.contains("Time limit of 0.05s reached"), "{stderr}");
+}
+
+#[test]
+fn stops_at_call_depth() {
+    let limits = Limits {
+        max_call_depth: Some(50),
+        ..Limits::none()
+    };
+    let (exit_code, stderr) = run_with(ENDLESS_LOOP, limits);
+
+    assert_eq!(exit_code, 1);
+    assert!(
+        stderr.contains("Call depth limit of 50 reached in label `loop`"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn default_call_depth_fits_in_a_spawned_thread() {
+    // Jumping through nested commands takes the most stack per level
+    let code = "label .ENTRY\n    var i 0\n    jmp loop\nlabel loop\n    add i i 1\n    cmd_eq i i cmd_eq 1 1 jmp loop";
+
+    let (exit_code, stderr) = thread::spawn(|| run_with(code, Limits::default()))
+        .join()
+        .unwrap();
+
+    assert_eq!(exit_code, 1);
+    assert!(
+        stderr.contains(&format!(
+            "Call depth limit of {DEFAULT_MAX_CALL_DEPTH} reached"
+        )),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn stops_at_memory_limit() {
+    let limits = Limits {
+        max_memory: Some(200),
+        ..Limits::default()
+    };
+    let code = "label .ENTRY\n    list_new l\n    jmp grow\nlabel grow\n    push l \"0123456789\"\n    jmp grow";
+    let (exit_code, stderr) = run_with(code, limits);
+
+    assert_eq!(exit_code, 1);
+    assert!(
+        stderr.contains("Memory limit of 200 bytes reached in label `grow`"),
+        "{stderr}"
+    );
+}
@@ -0,0 +1,91 @@
+use script_ll_2::linter::{lint, to_json};
+
+const CODE: &str = "label .ENTRY
+    var unused 1
+    var _ignored 2
+    var name \"Ann\"
+    test_eq name \"Ann\"
+    print \"Hello {name}\"
+    cmd_eq TEMP true print \"Equal\"
+    cmd_eq name name print \"Always\"
+    jmp helper
+
+label helper
+    print \"Helping\"
+
+label orphan
+    print \"Never runs\"
+
+label test_orphan_is_fine
+    var allowed 1 # lint: allow(unused-variable)
+";
+
+fn rules(disabled: &[&str]) -> Vec<(&'static str, usize)> {
+    let disabled = disabled
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>();
+    return lint(CODE, &disabled)
+        .unwrap()
+        .into_iter()
+        .map(|lint| (lint.rule, lint.line_number))
+        .collect()
+}
+
+#[test]
+fn finds_every_rule() {
+    assert_eq!(
+        rules(&[]),
+        vec![
+            ("unused-variable", 2),
+            ("stale-temp", 7),
+            ("self-comparison", 8),
+            ("unreachable-label", 14),
+        ]
+    );
+}
+
+#[test]
+fn rules_can_be_disabled() {
+    assert_eq!(
+        rules(&["unused-variable", "stale-temp"]),
+        vec![("self-comparison", 8), ("unreachable-label", 14)]
+    );
+}
+
+#[test]
+fn temp_read_right_after_a_jump_is_fine() {
+    let code = "label .ENTRY\n    jmp check\n    cmd_eq TEMP true print \"yes\"\n    cmd_eq TEMP false print \"no\"\n\nlabel check\n    test_gt 2 1";
+
+    assert!(lint(code, &[]).unwrap().is_empty());
+}
+
+#[test]
+fn only_compared_values_are_checked() {
+    let code =
+        "label .ENTRY\n    var n 2\n    add sum n n\n    assert_ne sum sum\n    list_new items sum";
+    let lints = lint(code, &[])
+        .unwrap()
+        .into_iter()
+        .map(|lint| (lint.rule, lint.line_number))
+        .collect::<Vec<(&str, usize)>>();
+
+    assert_eq!(lints, vec![("self-comparison", 4), ("unused-variable", 5)]);
+}
+
+#[test]
+fn prints_json() {
+    let lints = lint("label .ENTRY\n    cmd_eq \"a\" \"a\" print \"x\"", &[]).unwrap();
+
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&to_json(&lints)).unwrap(),
+        serde_json::json!([{
+            "rule": "self-comparison",
+            "line": 2,
+            "code": "cmd_eq \"a\" \"a\" print \"x\"",
+            "message": "`cmd_eq` compares `\"a\"` with itself",
+            "help": "The result never changes, compare with another value",
+        }])
+    );
+    assert_eq!(to_json(&[]), "[]");
+}
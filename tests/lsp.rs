@@ -0,0 +1,186 @@
+use std::io::BufRead;
+
+use serde_json::{json, Value as Json};
+
+use script_ll_2::lsp::serve;
+
+const URI: &str = "file:///script.ll";
+const CODE: &str = "label .ENTRY
+    var name \"Ann\"
+    jmp greet
+    prnt name
+
+label greet
+    print \"Hello {name}\"
+";
+
+fn frame(message: &Json) -> String {
+    let body = message.to_string();
+    return format!("Content-Length: {}\r\n\r\n{body}", body.len())
+}
+
+fn request(id: u64, method: &str, params: Json) -> String {
+    return frame(&json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params}))
+}
+
+fn at(line: u64, character: u64) -> Json {
+    return json!({"textDocument": {"uri": URI}, "position": {"line": line, "character": character}})
+}
+
+// Opens `code`, sends the messages to the server and returns everything it answered
+fn exchange_with(code: &str, messages: &[String]) -> Vec<Json> {
+    let open = frame(&json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/didOpen",
+        "params": {"textDocument": {"uri": URI, "languageId": "script-ll", "version": 1, "text": code}},
+    }));
+    let input = [vec![open], messages.to_vec()].concat().concat();
+    let mut output = Vec::new();
+    serve(&mut input.as_bytes(), &mut output).unwrap();
+
+    let mut output = output.as_slice();
+    let mut responses = Vec::new();
+    loop {
+        let mut header = String::new();
+        if output.read_line(&mut header).unwrap() == 0 {
+            return responses
+        }
+        let length: usize = header.trim_end()["Content-Length: ".len()..]
+            .parse()
+            .unwrap();
+        output.read_line(&mut String::new()).unwrap();
+        responses.push(serde_json::from_slice(&output[..length]).unwrap());
+        output = &output[length..];
+    }
+}
+
+fn exchange(messages: &[String]) -> Vec<Json> {
+    exchange_with(CODE, messages)
+}
+
+#[test]
+fn publishes_diagnostics() {
+    let responses = exchange(&[]);
+
+    assert_eq!(responses[0]["method"], "textDocument/publishDiagnostics");
+    let diagnostics = &responses[0]["params"]["diagnostics"];
+    assert_eq!(diagnostics.as_array().unwrap().len(), 1);
+    assert_eq!(diagnostics[0]["range"]["start"]["line"], 3);
+    assert!(diagnostics[0]["message"]
+        .as_str()
+        .unwrap()
+        .contains("Unknown command `prnt`"));
+}
+
+#[test]
+fn counts_blank_lines_at_the_start() {
+    let responses = exchange_with("\n\nlabel .ENTRY\n    prnt 1\n", &[]);
+
+    let diagnostics = &responses[0]["params"]["diagnostics"];
+    assert_eq!(diagnostics[0]["range"]["start"]["line"], 3);
+}
+
+#[test]
+fn finds_definitions_and_references() {
+    let responses = exchange(&[
+        request(1, "textDocument/definition", at(2, 9)),
+        request(2, "textDocument/references", at(1, 9)),
+    ]);
+
+    assert_eq!(
+        responses[1]["result"]["range"]["start"],
+        json!({"line": 5, "character": 6})
+    );
+    let references = responses[2]["result"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|location| return location["range"]["start"].clone())
+        .collect::<Vec<Json>>();
+    assert_eq!(
+        references,
+        vec![
+            json!({"line": 1, "character": 8}),
+            json!({"line": 3, "character": 9}),
+            json!({"line": 6, "character": 18}),
+        ]
+    );
+}
+
+#[test]
+fn hovers_completes_and_lists_labels() {
+    let responses = exchange(&[
+        request(1, "textDocument/hover", at(1, 5)),
+        request(2, "textDocument/completion", at(2, 7)),
+        request(3, "textDocument/completion", at(2, 9)),
+        request(4, "textDocument/documentSymbol", at(0, 0)),
+        request(5, "shutdown", Json::Null),
+        frame(&json!({"jsonrpc": "2.0", "method": "exit"})),
+    ]);
+
+    assert!(responses[1]["result"]["contents"]["value"]
+        .as_str()
+        .unwrap()
+        .contains("var <variable_name> <variable_value>"));
+    assert_eq!(responses[2]["result"][0]["label"], "jmp");
+    assert_eq!(
+        responses[3]["result"],
+        json!([{"label": "greet", "kind": 18}])
+    );
+    let symbols = responses[4]["result"].as_array().unwrap();
+    assert_eq!(
+        symbols
+            .iter()
+            .map(|symbol| symbol["name"].clone())
+            .collect::<Vec<Json>>(),
+        vec![json!(".ENTRY"), json!("greet")]
+    );
+    assert_eq!(responses[5]["result"], Json::Null);
+}
+
+#[test]
+fn skips_comments_and_strings() {
+    let code = "label .ENTRY
+    #[
+    label hidden
+    ]#
+    print \"\"\"
+        label fake
+        hi {name}
+        \"\"\" # jmp greet
+    var name 1
+
+label greet
+    cmt
+";
+    let responses = exchange_with(
+        code,
+        &[
+            request(1, "textDocument/definition", at(7, 19)),
+            request(2, "textDocument/references", at(8, 9)),
+            request(3, "textDocument/documentSymbol", at(0, 0)),
+        ],
+    );
+
+    assert_eq!(responses[1]["result"], Json::Null);
+    let references = responses[2]["result"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|location| location["range"]["start"].clone())
+        .collect::<Vec<Json>>();
+    assert_eq!(
+        references,
+        vec![
+            json!({"line": 6, "character": 12}),
+            json!({"line": 8, "character": 8}),
+        ]
+    );
+    let symbols = responses[3]["result"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|symbol| symbol["name"].clone())
+        .collect::<Vec<Json>>();
+    assert_eq!(symbols, vec![json!(".ENTRY"), json!("greet")]);
+}
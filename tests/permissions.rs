@@ -0,0 +1,98 @@
+mod common;
+
+use std::path::Path;
+
+use script_ll_2::permissions::{PathAccess, Permissions};
+
+fn run_with(code: &str, permissions: Permissions) -> (i32, String) {
+    let scripts_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/scripts");
+
+    common::run_with(code, |interpreter| {
+        interpreter.set_script_dir(scripts_dir);
+        interpreter.set_permissions(permissions);
+    })
+}
+
+fn read_only(root: &str) -> Permissions {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join(root);
+    Permissions {
+        read: PathAccess::Only(vec![root]),
+        ..Permissions::none()
+    }
+}
+
+#[test]
+fn denies_everything_in_sandbox() {
+    let code = "label .ENTRY\n    read_file x \"data/config.txt\"";
+    let (exit_code, stderr) = run_with(code, Permissions::none());
+
+    assert_eq!(exit_code, 1);
+    assert!(
+        stderr.contains("Permission denied: `read_file` needs read access to `data/config.txt`")
+    );
+}
+
+#[test]
+fn allows_paths_inside_root() {
+    let code = "label .ENTRY\n    read_file x \"data/config.txt\"";
+    let (exit_code, stderr) = run_with(code, read_only("tests/scripts/data"));
+
+    assert_eq!(exit_code, 0, "{stderr}");
+}
+
+#[test]
+fn denies_paths_escaping_root() {
+    let code = "label .ENTRY\n    read_file x \"data/../file_io.ll\"";
+    let (exit_code, stderr) = run_with(code, read_only("tests/scripts/data"));
+
+    assert_eq!(exit_code, 1);
+    assert!(stderr.contains("Permission denied"), "{stderr}");
+}
+
+#[test]
+fn permission_errors_can_be_caught() {
+    let code = "label .ENTRY\n    try write failed\n\nlabel write\n    write_file \"x.txt\" \"x\"\n\nlabel failed\n    assert_eq ERR_KIND \"PermissionError\"";
+    let (exit_code, stderr) = run_with(code, Permissions::none());
+
+    assert_eq!(exit_code, 0, "{stderr}");
+}
+
+#[test]
+fn gates_environment_access() {
+    let code = "label .ENTRY\n    env_set \"SCRIPT_LL_TEST_GATED\" \"1\"";
+    let (exit_code, stderr) = run_with(code, Permissions::none());
+
+    assert_eq!(exit_code, 1);
+    assert!(
+        stderr.contains("Permission denied: `env_set` needs env access"),
+        "{stderr}"
+    );
+
+    let env_only = Permissions {
+        env: true,
+        ..Permissions::none()
+    };
+    let (exit_code, stderr) = run_with(code, env_only);
+
+    assert_eq!(exit_code, 0, "{stderr}");
+}
+
+#[test]
+fn gates_running_programs() {
+    let code = "label .ENTRY\n    exec out \"echo\" \"hi\"";
+    let (exit_code, stderr) = run_with(code, Permissions::none());
+
+    assert_eq!(exit_code, 1);
+    assert!(
+        stderr.contains("Permission denied: `exec` needs run access"),
+        "{stderr}"
+    );
+
+    let run_only = Permissions {
+        run: true,
+        ..Permissions::none()
+    };
+    let (exit_code, stderr) = run_with(code, run_only);
+
+    assert_eq!(exit_code, 0, "{stderr}");
+}
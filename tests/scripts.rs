@@ -0,0 +1,104 @@
+// Runs every `.ll` file in `tests/scripts` and compares its exit code, stdout and stderr with
+// the `.expected` file next to it. Run with `BLESS=1 cargo test` to update the expected files.
+
+use std::{
+    cell::RefCell,
+    env, fs,
+    io::{self, Write},
+    path::Path,
+    rc::Rc,
+    thread,
+};
+
+use script_ll_2::interpreter::Interpreter;
+use script_ll_2::limits::{Limits, LARGE_STACK_SIZE};
+
+#[derive(Clone, Default)]
+struct SharedBuffer(Rc<RefCell<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn run_script(path: &Path) -> String {
+    let code = fs::read_to_string(path).unwrap();
+    let stdout = SharedBuffer::default();
+    let mut stderr = Vec::new();
+
+    let configure = |interpreter: &mut Interpreter| {
+        interpreter.set_output(Box::new(stdout.clone()));
+        interpreter.set_script_dir(path.parent().unwrap().to_path_buf());
+        interpreter.set_limits(Limits::large_stack());
+    };
+    let exit_code = script_ll_2::run(code, configure, &mut stderr);
+
+    let stdout = String::from_utf8(stdout.0.borrow().clone()).unwrap();
+    let stderr = String::from_utf8(stderr).unwrap();
+
+    format!("[Exit code]\n{exit_code}\n[Stdout]\n{stdout}[Stderr]\n{stderr}")
+}
+
+// Runs a script the way the command line does, with its stack and call depth
+fn run_script_on_large_stack(path: &Path) -> String {
+    let path = path.to_path_buf();
+    thread::Builder::new()
+        .stack_size(LARGE_STACK_SIZE)
+        .spawn(move || run_script(&path))
+        .unwrap()
+        .join()
+        .unwrap()
+}
+
+#[test]
+fn scripts() {
+    let bless = env::var_os("BLESS").is_some();
+    let scripts_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/scripts");
+
+    let mut scripts = fs::read_dir(&scripts_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "ll"))
+        .collect::<Vec<_>>();
+    scripts.sort();
+    assert!(!scripts.is_empty(), "no scripts found in {scripts_dir:?}");
+
+    let mut failures = Vec::new();
+
+    for script in &scripts {
+        let actual = run_script_on_large_stack(script);
+        let expected_path = script.with_extension("expected");
+
+        if bless {
+            fs::write(&expected_path, &actual).unwrap();
+            continue;
+        }
+
+        match fs::read_to_string(&expected_path) {
+            Ok(expected) if expected == actual => {}
+            Ok(expected) => failures.push(format!(
+                "{}\n--- expected ---\n{expected}--- actual ---\n{actual}",
+                script.display()
+            )),
+            Err(_) => failures.push(format!(
+                "{}\nmissing {}, run with BLESS=1 to create it\n--- actual ---\n{actual}",
+                script.display(),
+                expected_path.display()
+            )),
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{} of {} scripts did not match:\n\n{}",
+        failures.len(),
+        scripts.len(),
+        failures.join("\n")
+    );
+}
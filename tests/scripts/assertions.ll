@@ -0,0 +1,19 @@
+label .ENTRY
+    assert true
+    assert_eq 0x2A 42
+    assert_ne "a" "b"
+    var name "Ann"
+    assert_eq name "Ann"
+    test_eq "5" 5
+    assert_eq TEMP true
+    assert_eq "5" 5
+    print "passed"
+    try failing report
+    assert_eq 1 2 "one is not two"
+
+label failing
+    var x 3
+    assert_eq x 4
+
+label report
+    print "{ERR_KIND}: {ERR_MSG}"
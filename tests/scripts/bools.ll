@@ -0,0 +1,17 @@
+label .ENTRY
+    test_lt 1 2 lt
+    test_gt 1 2
+    and a lt TEMP
+    or o lt TEMP
+    xor x lt TEMP
+    not n lt
+    print "{lt} {TEMP} {a} {o} {x} {n}"
+    test_gt_eq 2 2
+    test_lt_eq 3 2 le
+    print "{TEMP} {le}"
+    cmd_eq TEMP 1 print "true equals 1"
+    cmd_eq le 0 print "false equals 0"
+    test_eq true 1 same
+    test_eq true false different
+    print "{same} {different}"
+    and bad 1 true
@@ -0,0 +1,10 @@
+label .ENTRY
+    var depth 0
+    try countdown handler
+
+label countdown
+    add depth depth 1
+    jmp countdown
+
+label handler
+    print "never printed, limits cannot be caught"
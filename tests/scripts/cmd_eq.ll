@@ -0,0 +1,20 @@
+label .ENTRY
+    var a 1
+    var b 2
+    cmd_eq a 1 print "a is 1"
+    cmd_eq a b print "a equals b"
+    cmd_eq b 2 print "b is 2"
+    cmd_eq a a print "a equals itself"
+    cmd_eq "x" "x" cmd_eq b 2 print "nested cmd_eq"
+
+    var x 16
+    cmd_eq x 0x10 print "x is 0x10"
+    cmd_eq 2.0 b print "2.0 is b"
+
+    var name "Ann"
+    cmd_eq name "Ann" print "variable equals literal"
+    cmd_eq "Ann" name print "literal equals variable"
+    cmd_eq "5" 5 print "string 5 equals number 5"
+    test_eq name "Ann" same
+    test_eq name "Bo" different
+    print "{same} {different}"
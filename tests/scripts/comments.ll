@@ -0,0 +1,10 @@
+# Comments can fill a whole line
+label .ENTRY # or follow a command
+    var greeting "Hello # not a comment" # a comment
+    print greeting#right after a token
+    #[ A block comment
+       print "never runs"
+    ]#
+    print #[ inline ]# "after the block"
+    cmt the old comment command still works
+    prnt "line numbers are kept"
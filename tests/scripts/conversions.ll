@@ -0,0 +1,20 @@
+label .ENTRY
+    var input " 42 "
+    typeof t input
+    is_num input
+    print "{t} {TEMP}"
+    to_num n input
+    add n n 1
+    typeof t n
+    print "{n} {t}"
+    to_str s 0xFF
+    is_str s
+    print "{s} {TEMP}"
+    var zip "0150"
+    to_str a "007"
+    to_str b zip
+    to_str c "0x1F"
+    print "{a} {b} {c}"
+    is_num "abc"
+    print TEMP
+    to_num bad "abc"
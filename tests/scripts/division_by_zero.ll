@@ -0,0 +1,2 @@
+label .ENTRY
+    div x 1 0
@@ -0,0 +1,11 @@
+label .ENTRY
+    print "{ARGC} arguments: {ARGV}"
+
+    env_get missing "SCRIPT_LL_TEST_MISSING" "default"
+    print "missing: {missing}"
+
+    env_set "SCRIPT_LL_TEST_GREETING" "hello {missing}"
+    env_get greeting "SCRIPT_LL_TEST_GREETING"
+    print "greeting: {greeting}"
+
+    env_get missing "SCRIPT_LL_TEST_MISSING"
@@ -0,0 +1,19 @@
+label .ENTRY
+    exec greeting "echo" "hello" "{{not interpolated}}" "$HOME; exit 1"
+    print "stdout: {greeting}"
+    print "status: {EXEC_STATUS}"
+
+    exec out "sh" "-c" "echo failed >&2; exit 3"
+    print "stderr: {EXEC_STDERR}"
+    print "status: {EXEC_STATUS}"
+
+    exec_inherit "true"
+    print "inherited status: {EXEC_STATUS}"
+
+    try missing not_found
+
+label missing
+    exec out "script-ll-no-such-program"
+
+label not_found
+    print "{ERR_KIND}: {ERR_MSG}"
@@ -0,0 +1,23 @@
+label .ENTRY
+    read_lines lines "data/config.txt"
+    len n lines
+    print "{n} lines: {lines}"
+
+    file_exists "data/config.txt"
+    file_exists "data/missing.txt" missing
+    print "{TEMP} {missing}"
+
+    var out "../../target/file_io_test.txt"
+    write_file out "first"
+    append_file out " second"
+    read_file content out
+    print content
+
+    try read_missing failed
+    read_file x "data/missing.txt"
+
+label read_missing
+    read_file x "data/missing.txt"
+
+label failed
+    print "{ERR_KIND}: {ERR_MSG}"
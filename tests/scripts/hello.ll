@@ -0,0 +1,14 @@
+cmt Hello world
+
+label .ENTRY
+    print "Hello World!"
+    
+    var age 21
+
+    jmp check_driver_eligibility
+    cmd_eq TEMP true print "You can drive!"
+    cmd_eq TEMP false print "You can't drive yet :C"
+
+label check_driver_eligibility
+    require age
+    test_gt_eq age 18
\ No newline at end of file
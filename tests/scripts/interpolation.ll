@@ -0,0 +1,9 @@
+label .ENTRY
+    var name "World"
+    var age 21
+    print "Hello {name}, you are {age}"
+    print "{{escaped}} and }}"
+    var greeting "Hi {name}"
+    var name "Ann"
+    print greeting
+    print "{missing}"
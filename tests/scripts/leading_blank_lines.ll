@@ -0,0 +1,6 @@
+
+
+
+label .ENTRY
+    print "a"
+    prnt "b"
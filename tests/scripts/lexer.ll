@@ -0,0 +1,9 @@
+cmt Strings keep their spaces, everything else is split on whitespace
+
+label .ENTRY
+	print "two  spaces and a tab	inside"
+    print   "extra   spacing between arguments"
+    var   name    "Ann"
+    print name
+    print "line one\nline two"
+    print ""
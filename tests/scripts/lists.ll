@@ -0,0 +1,23 @@
+label .ENTRY
+    list_new xs 3 1 "two" 2
+    push xs 10
+    pop last xs
+    print "popped {last}"
+    remove xs 2
+    sort xs
+    print xs
+    insert xs 0 "first"
+    set xs 1 "one"
+    get item xs 1
+    len n xs
+    print "{item} of {n}: {xs}"
+    join s xs ", "
+    print s
+    list_new ys "first" "one" 2 3
+    test_eq xs ys
+    print TEMP
+    foreach x xs show
+    get z xs 9
+
+label show
+    print "- {x}"
@@ -0,0 +1,9 @@
+label .ENTRY
+    var i 0
+    jmp loop
+    print "done {i}"
+
+label loop
+    add i i 1
+    test_lt i 5000
+    cmd_eq TEMP true jmp loop
@@ -0,0 +1,15 @@
+label .ENTRY
+    map_new user
+    map_set user "name" "Ann"
+    map_set user "age" 30
+    map_set user "city" "Oslo"
+    map_del user "city"
+    map_get city user "city" "unknown"
+    map_has user "age" has_age
+    map_keys keys user
+    map_len n user
+    print "{user} {city} {has_age} {keys} {n}"
+    var alias user
+    map_set alias "zip" "0150"
+    print user
+    map_get x user "nope"
@@ -0,0 +1,2 @@
+label main
+    print "never printed"
@@ -0,0 +1,17 @@
+label .ENTRY
+    print """
+        Dear {{name}},
+
+        	Tabs and "quotes" # stay
+        Bye
+        """
+    var name "Ann"
+    print """one line"""
+    var kept """
+starts at column 0
+  indented
+"""
+    print kept
+    print """first
+    second""" # trailing comment
+    prnt "reported on line 17"
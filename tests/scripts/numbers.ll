@@ -0,0 +1,19 @@
+label .ENTRY
+    test_gt 16777217 16777216
+    print TEMP
+    test_eq 0x10 16
+    print TEMP
+    test_eq 16 16.0
+    print TEMP
+    test_lt -1_000 0b11
+    print TEMP
+    add a 9223372036854775806 1
+    mul b 2.5 4
+    div c 7 2
+    div d 7.0 2
+    mod e -7 3
+    to_float f 3
+    to_int g -3.9
+    mul h 1e200 1.5
+    print "{a} {b} {c} {d} {e} {f} {g} {h}"
+    add z a 1
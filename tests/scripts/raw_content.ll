@@ -0,0 +1,10 @@
+label .ENTRY
+    read_file content "data/raw.txt"
+    print content
+
+    var copy content
+    list_new lines content
+    print copy
+    print "{lines}"
+
+    print "a\nb \\n C:\\dir"
@@ -0,0 +1,4 @@
+#!/usr/bin/env script-ll-2
+label .ENTRY
+    print "Shebang lines are skipped"
+    prnt "line numbers still count it"
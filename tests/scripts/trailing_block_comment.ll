@@ -0,0 +1,8 @@
+label .ENTRY
+    print "the last label still runs"
+    jmp last
+
+label last
+    print "and so does this one"
+    #[ a block comment
+       at the end of the script ]#
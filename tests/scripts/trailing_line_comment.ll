@@ -0,0 +1,7 @@
+label .ENTRY
+    print "the last label still runs"
+    jmp last
+
+label last
+    print "and so does this one"
+# a comment on the last line
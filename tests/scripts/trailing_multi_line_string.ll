@@ -0,0 +1,6 @@
+label .ENTRY
+    print "the string below ends the script"
+    print """
+        first line
+        second line
+        """
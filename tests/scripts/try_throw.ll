@@ -0,0 +1,20 @@
+label .ENTRY
+    var input "abc"
+    try parse failed
+    try custom failed
+    try fine failed
+    print "done"
+    throw "Uncaught {input}"
+
+label parse
+    to_num n input
+    print "never printed"
+
+label custom
+    throw "Custom {input}"
+
+label fine
+    print "fine"
+
+label failed
+    print "{ERR_KIND} at line {ERR_LINE}: {ERR_MSG}"
@@ -0,0 +1,4 @@
+label .ENTRY
+    print "before"
+    #[ this never ends
+    print "after"
@@ -0,0 +1,4 @@
+label .ENTRY
+    print "ok"
+    print """never
+    ended
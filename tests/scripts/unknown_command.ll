@@ -0,0 +1,3 @@
+label .ENTRY
+    print "before"
+    frobnicate 1 2
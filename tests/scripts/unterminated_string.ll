@@ -0,0 +1,2 @@
+label .ENTRY
+    print "Hello
@@ -0,0 +1,16 @@
+label .ENTRY
+    var s "a"
+    var s "{s}b"
+    var s "{s}c"
+    print s
+
+    var copy s
+    var s "changed"
+    print "{copy} {s}"
+
+    list_new items 1 2
+    var other items
+    push other 3
+    print "{items} {other}"
+    var items other
+    print items
@@ -0,0 +1,2 @@
+label .ENTRY
+    var x